@@ -1,5 +1,8 @@
 # for this to work on local machine you need to replace call to local ones
 # same with ids
+# backend picks the proxy from its init argument, e.g.
+# dfx deploy trendlens_backend --argument '(opt record { proxy_canister_id = opt principal "<local proxy id>"; admins = null })'
+# or from set_proxy_canister afterwards
 
 MYID=$(dfx identity get-principal)
 dfx canister call --ic idempotency-proxy admin_set_managers "(vec {principal \"$MYID\"})"
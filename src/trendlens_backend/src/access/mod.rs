@@ -0,0 +1,148 @@
+use crate::{
+    memory::{Memory, MemoryLocation, MEMORY_MANAGER},
+    storable_wrapper::{StorableWrapper, Versioned},
+};
+use candid::{CandidType, Principal};
+use ic_stable_structures::StableBTreeMap;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+type RolesTable = StableBTreeMap<Principal, StorableWrapper<Role>, Memory>;
+
+thread_local! {
+    static ROLES: RefCell<RolesTable> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Roles.memory_id())),
+        )
+    );
+}
+
+// variants are persisted by index, new roles are appended at the end
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// manages canister configuration and other principals' roles
+    Admin,
+    /// maintains global market data, which spends canister cycles on outcalls
+    Operator,
+    /// trades with own api keys
+    Trader,
+    /// no privileges, every principal without a stored role
+    Anonymous,
+}
+
+impl Versioned for Role {}
+
+impl Role {
+    fn level(self) -> u8 {
+        match self {
+            Role::Admin => 3,
+            Role::Operator => 2,
+            Role::Trader => 1,
+            Role::Anonymous => 0,
+        }
+    }
+
+    /// roles include privileges of all lower roles
+    pub fn allows(self, required: Role) -> bool {
+        self.level() >= required.level()
+    }
+}
+
+pub struct Access;
+
+impl Access {
+    pub fn role(principal: &Principal) -> Option<Role> {
+        ROLES.with_borrow(|r| r.get(principal).map(|r| r.0))
+    }
+
+    pub fn grant(principal: Principal, role: Role) {
+        ROLES.with_borrow_mut(|r| r.insert(principal, StorableWrapper(role)));
+    }
+
+    pub fn revoke(principal: &Principal) -> Option<Role> {
+        ROLES.with_borrow_mut(|r| r.remove(principal).map(|r| r.0))
+    }
+
+    pub fn get_roles() -> Vec<(Principal, Role)> {
+        ROLES.with_borrow(|r| r.iter().map(|(p, r)| (p, r.0)).collect())
+    }
+
+    /// controllers are always admins, even without a stored role
+    pub fn effective_role(principal: &Principal) -> Role {
+        if ic_cdk::api::is_controller(principal) {
+            return Role::Admin;
+        }
+
+        Self::stored_role(principal)
+    }
+
+    fn stored_role(principal: &Principal) -> Role {
+        match *principal == Principal::anonymous() {
+            true => Role::Anonymous,
+            false => Self::role(principal).unwrap_or(Role::Anonymous),
+        }
+    }
+}
+
+fn require(required: Role) -> Result<(), String> {
+    match Access::effective_role(&ic_cdk::caller()).allows(required) {
+        true => Ok(()),
+        false => Err(format!("caller is missing {:?} role", required)),
+    }
+}
+
+pub fn is_admin() -> Result<(), String> {
+    require(Role::Admin)
+}
+
+pub fn is_operator() -> Result<(), String> {
+    require(Role::Operator)
+}
+
+pub fn is_trader() -> Result<(), String> {
+    require(Role::Trader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grant_and_revoke_role() {
+        let principal = Principal::from_text("aaaaa-aa").unwrap();
+
+        assert_eq!(Access::role(&principal), None);
+
+        Access::grant(principal, Role::Admin);
+
+        assert_eq!(Access::role(&principal), Some(Role::Admin));
+        assert_eq!(Access::get_roles(), vec![(principal, Role::Admin)]);
+
+        assert_eq!(Access::revoke(&principal), Some(Role::Admin));
+        assert_eq!(Access::role(&principal), None);
+    }
+
+    #[test]
+    fn test_role_hierarchy() {
+        assert!(Role::Admin.allows(Role::Operator));
+        assert!(Role::Operator.allows(Role::Trader));
+        assert!(Role::Trader.allows(Role::Trader));
+        assert!(!Role::Trader.allows(Role::Operator));
+        assert!(!Role::Anonymous.allows(Role::Trader));
+    }
+
+    #[test]
+    fn test_anonymous_principal_has_no_role() {
+        let anonymous = Principal::anonymous();
+
+        // granting a role to the anonymous principal has no effect
+        Access::grant(anonymous, Role::Trader);
+        assert_eq!(Access::stored_role(&anonymous), Role::Anonymous);
+
+        let principal = Principal::from_text("aaaaa-aa").unwrap();
+        assert_eq!(Access::stored_role(&principal), Role::Anonymous);
+
+        Access::grant(principal, Role::Operator);
+        assert_eq!(Access::stored_role(&principal), Role::Operator);
+    }
+}
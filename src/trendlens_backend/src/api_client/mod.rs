@@ -1,18 +1,20 @@
-use crate::remote_exchanges::{
-    response::ApiResponseWrapper, ApiRequest, Authorize, ExchangeErrors, PathFormatter,
+use crate::{
+    exchange::Exchange,
+    quota::Quota,
+    remote_exchanges::{response::ApiResponseWrapper, ApiRequest, Authorize, ExchangeErrors},
 };
-use candid::{CandidType, Nat, Principal};
+use candid::{CandidType, Nat};
 use ic_cdk::api::{
     call::RejectionCode,
-    management_canister::http_request::{CanisterHttpRequestArgument, HttpHeader, HttpResponse},
+    management_canister::http_request::{CanisterHttpRequestArgument, HttpHeader},
 };
-use ic_stable_structures::Storable;
-use std::{cell::RefCell, str::FromStr};
+use std::cell::RefCell;
 use thiserror::Error;
+use transport::{required_cycles, TransportKind};
 
-thread_local! {
-    static PROXY_CANISTER_ID: RefCell<Principal> = RefCell::new(Principal::from_str("ahdfa-wyaaa-aaaal-ajpba-cai").unwrap());
+pub mod transport;
 
+thread_local! {
     static IDEMPOTENCY_COUNTER: RefCell<u64> = RefCell::new(0);
 }
 
@@ -25,12 +27,20 @@ pub enum ApiClientErrors {
         message: String,
         code: RejectionCode,
     },
+    #[error("{method} requests need a proxy that honors X-HTTP-Method-Override")]
+    UnsupportedMethod { method: String },
 }
 
-#[derive(Default)]
-pub struct ApiClient;
+/// sends requests of one exchange through the transport selected for it
+pub struct ApiClient {
+    exchange: Exchange,
+}
 
 impl ApiClient {
+    pub fn new(exchange: Exchange) -> Self {
+        Self { exchange }
+    }
+
     fn get_contract_headers(_host: &'static str) -> Vec<HttpHeader> {
         vec![
             HttpHeader {
@@ -52,20 +62,6 @@ impl ApiClient {
         ]
     }
 
-    fn get_required_cycles(arg: &CanisterHttpRequestArgument) -> u128 {
-        let max_response_bytes = match arg.max_response_bytes {
-            Some(ref n) => *n as u128,
-            None => 2 * 1024 * 1024u128, // default 2MiB
-        };
-        let arg_raw = candid::utils::encode_args((arg,)).expect("Failed to encode arguments.");
-        // The fee is for a 13-node subnet to demonstrate a typical usage.
-        (3_000_000u128
-            + 60_000u128 * 13
-            + (arg_raw.len() as u128 + "http_request".len() as u128) * 400
-            + max_response_bytes * 800)
-            * 13
-    }
-
     fn get_headers<A, R>(auth: Option<&A>) -> Vec<HttpHeader>
     where
         A: Authorize,
@@ -77,12 +73,73 @@ impl ApiClient {
             vec![]
         };
 
-        let joined_headers = [auth_headers, Self::get_contract_headers(R::HOST)].concat();
-        ic_cdk::println!("HEADERS: {:?}", joined_headers);
+        let override_headers = R::METHOD_OVERRIDE
+            .map(|method| {
+                vec![HttpHeader {
+                    name: "X-HTTP-Method-Override".to_string(),
+                    value: method.to_string(),
+                }]
+            })
+            .unwrap_or_default();
+
+        let joined_headers = [
+            auth_headers,
+            override_headers,
+            Self::get_contract_headers(R::HOST),
+        ]
+        .concat();
+        log!("HEADERS: {:?}", joined_headers);
 
         joined_headers
     }
 
+    /// outcalls made by the canister itself (scheduled jobs) are not limited
+    fn charge_caller(request: &CanisterHttpRequestArgument) -> Result<(), ExchangeErrors> {
+        let caller = ic_cdk::caller();
+
+        if caller == ic_cdk::id() {
+            return Ok(());
+        }
+
+        Quota::charge(
+            &caller,
+            ic_cdk::api::time() / 1_000_000_000,
+            required_cycles(request),
+        )
+    }
+
+    /// http request for the exchange request, its method, path, query and body
+    /// are the same parts exchanges sign
+    pub fn http_request<R, A>(request: &R, auth: Option<&A>) -> CanisterHttpRequestArgument
+    where
+        R: ApiRequest,
+        A: Authorize,
+    {
+        let parts = request.parts();
+        let auth_qs = auth.and_then(|a| a.get_auth_query()).unwrap_or_default();
+
+        let qs = match (parts.query.is_empty(), auth_qs.is_empty()) {
+            (true, true) => "".to_string(),
+            (false, true) => format!("?{}", parts.query),
+            (true, false) => format!("?{}", auth_qs),
+            (false, false) => format!("?{}&{}", parts.query, auth_qs),
+        };
+        log!("query: {}", qs);
+        log!("path: {}", parts.path);
+
+        let api_url = format!("https://{}/{}{}", R::HOST, parts.path, qs);
+        log!("{}", api_url);
+        log!("{:?}", parts.body);
+
+        CanisterHttpRequestArgument {
+            url: api_url,
+            method: R::METHOD,
+            headers: Self::get_headers::<_, R>(auth),
+            body: parts.body.map(|b| b.into_bytes()),
+            ..Default::default()
+        }
+    }
+
     pub async fn call<W, R, A>(
         &self,
         request: R,
@@ -93,51 +150,35 @@ impl ApiClient {
         A: Authorize,
         W: ApiResponseWrapper<R::Response>,
     {
-        let qs = if R::BODY {
-            "".to_string()
-        } else {
-            let qs = request.to_query_string();
-
-            if qs == "" {
-                "".to_string()
-            } else {
-                format!("?{}", qs)
-            }
-        };
-        ic_cdk::println!("query: {}", qs);
-
-        let path = if R::PATH_PARAMS {
-            request.get_path(R::URI)
-        } else {
-            R::URI.to_string()
-        };
-        ic_cdk::println!("path: {}", path);
+        self.call_with_headers::<W, R, A>(request, auth)
+            .await
+            .map(|(response, _)| response)
+    }
 
-        let api_url = format!("https://{}/{}{}", R::HOST, path, qs);
-        ic_cdk::println!("{}", api_url);
+    /// same as call, but also returns response headers, some exchanges
+    /// send pagination cursors in them
+    pub async fn call_with_headers<W, R, A>(
+        &self,
+        request: R,
+        auth: Option<&A>,
+    ) -> Result<(R::Response, Vec<HttpHeader>), ExchangeErrors>
+    where
+        R: ApiRequest,
+        A: Authorize,
+        W: ApiResponseWrapper<R::Response>,
+    {
+        let request = Self::http_request(&request, auth);
 
-        let body = R::BODY.then(|| request.to_body());
-        ic_cdk::println!("{:?}", body);
+        let transport = TransportKind::get(self.exchange);
 
-        let request = CanisterHttpRequestArgument {
-            url: api_url,
-            method: R::METHOD,
-            headers: Self::get_headers::<_, R>(auth),
-            body: body.and_then(|b| Some(b.to_bytes().to_vec())),
-            ..Default::default()
-        };
+        // replayed traffic does not spend any cycles
+        if transport != TransportKind::Replay {
+            Self::charge_caller(&request)?;
+        }
 
-        let required_cycles = Self::get_required_cycles(&request);
-        let (response,): (HttpResponse,) = ic_cdk::api::call::call_with_payment128(
-            PROXY_CANISTER_ID.with_borrow(|id| id.clone()),
-            "proxy_http_request",
-            (request,),
-            required_cycles,
-        )
-        .await
-        .map_err(|(code, message)| ApiClientErrors::Reject { code, message })?;
+        let response = transport.transport().send(request).await?;
 
-        ic_cdk::println!(
+        log!(
             "{:?}",
             String::from_utf8(response.body.clone()).expect("conversion failed")
         );
@@ -157,6 +198,8 @@ impl ApiClient {
                 message: e.to_string(),
             })?;
 
-        deserialized_response.extract_response()
+        deserialized_response
+            .extract_response()
+            .map(|r| (r, response.headers))
     }
 }
@@ -0,0 +1,375 @@
+use super::ApiClientErrors;
+use crate::{
+    config::Config,
+    exchange::Exchange,
+    memory::{Memory, MemoryLocation, MEMORY_MANAGER},
+    remote_exchanges::coinbase::CURSOR_HEADER,
+    storable_wrapper::{StorableWrapper, Versioned},
+};
+use candid::CandidType;
+use ic_cdk::api::management_canister::http_request::{
+    http_request, CanisterHttpRequestArgument, HttpMethod, HttpResponse, TransformContext,
+};
+use ic_stable_structures::StableBTreeMap;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+type TransportTable = StableBTreeMap<Exchange, StorableWrapper<TransportKind>, Memory>;
+
+/// query method used to make direct outcall responses identical across replicas
+pub const TRANSFORM_METHOD: &str = "transform_http_response";
+
+/// response headers kept by the transform, everything else differs between replicas
+const KEPT_HEADERS: &[&str] = &[CURSOR_HEADER];
+
+thread_local! {
+    static TRANSPORTS: RefCell<TransportTable> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Transports.memory_id())),
+        )
+    );
+
+    // captured traffic lives on the heap only, it is meant to be exported and replayed
+    static RECORDED: RefCell<Vec<RecordedCall>> = const { RefCell::new(vec![]) };
+}
+
+#[async_trait::async_trait]
+pub trait Transport: Send + Sync {
+    async fn send(
+        &self,
+        request: CanisterHttpRequestArgument,
+    ) -> Result<HttpResponse, ApiClientErrors>;
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransportKind {
+    /// idempotent proxy canister
+    #[default]
+    Proxy,
+    /// management canister outcall for reads, writes still use the proxy
+    Outcall,
+    /// goes through the proxy and captures the traffic
+    Record,
+    /// serves captured traffic without any call
+    Replay,
+}
+
+impl Versioned for TransportKind {}
+
+impl TransportKind {
+    pub fn get(exchange: Exchange) -> Self {
+        TRANSPORTS.with_borrow(|t| t.get(&exchange).map(|k| k.0).unwrap_or_default())
+    }
+
+    pub fn set(exchange: Exchange, kind: Self) {
+        TRANSPORTS.with_borrow_mut(|t| t.insert(exchange, StorableWrapper(kind)));
+    }
+
+    pub fn transport(self) -> Box<dyn Transport> {
+        match self {
+            TransportKind::Proxy => Box::new(ProxyTransport),
+            TransportKind::Outcall => Box::new(OutcallTransport),
+            TransportKind::Record => Box::new(Recorder(ProxyTransport)),
+            TransportKind::Replay => Box::new(Replayer),
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct RecordedCall {
+    pub method: HttpMethod,
+    pub url: String,
+    pub response: HttpResponse,
+}
+
+impl RecordedCall {
+    /// query strings carry timestamps and signatures, so calls are matched by path
+    fn matches(&self, request: &CanisterHttpRequestArgument) -> bool {
+        let path = |url: &str| url.split('?').next().unwrap_or_default().to_string();
+
+        self.method == request.method && path(&self.url) == path(&request.url)
+    }
+}
+
+pub fn required_cycles(arg: &CanisterHttpRequestArgument) -> u128 {
+    let max_response_bytes = match arg.max_response_bytes {
+        Some(ref n) => *n as u128,
+        None => 2 * 1024 * 1024u128, // default 2MiB
+    };
+    let arg_raw = candid::utils::encode_args((arg,)).expect("Failed to encode arguments.");
+    // The fee is for a 13-node subnet to demonstrate a typical usage.
+    (3_000_000u128
+        + 60_000u128 * 13
+        + (arg_raw.len() as u128 + "http_request".len() as u128) * 400
+        + max_response_bytes * 800)
+        * 13
+}
+
+/// strips everything replicas would disagree on
+pub fn transform_response(response: HttpResponse) -> HttpResponse {
+    HttpResponse {
+        headers: response
+            .headers
+            .into_iter()
+            .filter(|h| KEPT_HEADERS.iter().any(|k| h.name.eq_ignore_ascii_case(k)))
+            .collect(),
+        ..response
+    }
+}
+
+pub struct ProxyTransport;
+
+#[async_trait::async_trait]
+impl Transport for ProxyTransport {
+    async fn send(
+        &self,
+        request: CanisterHttpRequestArgument,
+    ) -> Result<HttpResponse, ApiClientErrors> {
+        check_method_override(&request)?;
+
+        let cycles = required_cycles(&request);
+        let (response,): (HttpResponse,) = ic_cdk::api::call::call_with_payment128(
+            Config::proxy_canister_id(),
+            "proxy_http_request",
+            (request,),
+            cycles,
+        )
+        .await
+        .map_err(|(code, message)| ApiClientErrors::Reject { code, message })?;
+
+        Ok(response)
+    }
+}
+
+fn method_override(request: &CanisterHttpRequestArgument) -> Option<&str> {
+    request
+        .headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case("X-HTTP-Method-Override"))
+        .map(|h| h.value.as_str())
+}
+
+/// the proxy would send an overridden request as the POST it receives, which
+/// exchanges reject or treat as another endpoint
+fn check_method_override(request: &CanisterHttpRequestArgument) -> Result<(), ApiClientErrors> {
+    match method_override(request) {
+        Some(method) if !Config::proxy_method_override() => {
+            Err(ApiClientErrors::UnsupportedMethod {
+                method: method.to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// every replica makes the outcall, so only plain reads can go out directly
+fn is_read_only(request: &CanisterHttpRequestArgument) -> bool {
+    request.method == HttpMethod::GET && method_override(request).is_none()
+}
+
+/// direct outcalls for reads, anything changing state on the exchange goes
+/// through the idempotent proxy
+pub struct OutcallTransport;
+
+#[async_trait::async_trait]
+impl Transport for OutcallTransport {
+    async fn send(
+        &self,
+        request: CanisterHttpRequestArgument,
+    ) -> Result<HttpResponse, ApiClientErrors> {
+        if !is_read_only(&request) {
+            return ProxyTransport.send(request).await;
+        }
+
+        let request = CanisterHttpRequestArgument {
+            transform: Some(TransformContext::from_name(
+                TRANSFORM_METHOD.to_string(),
+                vec![],
+            )),
+            ..request
+        };
+
+        let cycles = required_cycles(&request);
+        let (response,) = http_request(request, cycles)
+            .await
+            .map_err(|(code, message)| ApiClientErrors::Reject { code, message })?;
+
+        Ok(response)
+    }
+}
+
+/// traffic captured by the recorder so far
+pub fn recorded_traffic() -> Vec<RecordedCall> {
+    RECORDED.with_borrow(|r| r.clone())
+}
+
+/// replaces captured traffic, the replayer serves it from now on
+pub fn load_traffic(calls: Vec<RecordedCall>) {
+    RECORDED.with_borrow_mut(|r| *r = calls);
+}
+
+/// captures every successful call of the wrapped transport
+pub struct Recorder<T: Transport>(pub T);
+
+#[async_trait::async_trait]
+impl<T: Transport> Transport for Recorder<T> {
+    async fn send(
+        &self,
+        request: CanisterHttpRequestArgument,
+    ) -> Result<HttpResponse, ApiClientErrors> {
+        let method = request.method;
+        let url = request.url.clone();
+        let response = self.0.send(request).await?;
+
+        RECORDED.with_borrow_mut(|r| {
+            r.push(RecordedCall {
+                method,
+                url,
+                response: response.clone(),
+            })
+        });
+
+        Ok(response)
+    }
+}
+
+/// serves captured calls in the order they were recorded, each call once
+pub struct Replayer;
+
+#[async_trait::async_trait]
+impl Transport for Replayer {
+    async fn send(
+        &self,
+        request: CanisterHttpRequestArgument,
+    ) -> Result<HttpResponse, ApiClientErrors> {
+        RECORDED.with_borrow_mut(|r| {
+            let position = r.iter().position(|c| c.matches(&request)).ok_or_else(|| {
+                ApiClientErrors::Http {
+                    status: 404u32.into(),
+                    body: format!("no recorded call for {}", request.url),
+                }
+            })?;
+
+            Ok(r.remove(position).response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api_client::ApiClient,
+        remote_exchanges::okx::{
+            api::GetOrderBookRequest,
+            auth::OkxAuth,
+            response::{ApiResponse, OrderBook},
+        },
+    };
+    use futures::executor::block_on;
+    use ic_cdk::api::management_canister::http_request::HttpHeader;
+
+    #[test]
+    fn test_transform_keeps_cursor_header() {
+        let response = HttpResponse {
+            status: 200u32.into(),
+            headers: vec![
+                HttpHeader {
+                    name: "Date".to_string(),
+                    value: "Tue, 18 Jun 2024 10:00:00 GMT".to_string(),
+                },
+                HttpHeader {
+                    name: "CB-AFTER".to_string(),
+                    value: "42".to_string(),
+                },
+            ],
+            body: b"[]".to_vec(),
+        };
+
+        let transformed = transform_response(response);
+
+        assert_eq!(transformed.headers.len(), 1);
+        assert_eq!(transformed.headers[0].value, "42");
+        assert_eq!(transformed.body, b"[]".to_vec());
+    }
+
+    #[test]
+    fn test_replay_recorded_call() {
+        TransportKind::set(Exchange::Okx, TransportKind::Replay);
+        load_traffic(vec![RecordedCall {
+            method: HttpMethod::GET,
+            url: "https://www.okx.com/api/v5/market/books?instId=BTC-USD&sz=1".to_string(),
+            response: HttpResponse {
+                status: 200u32.into(),
+                headers: vec![],
+                body: br#"{"code":"0","msg":"","data":[{"asks":[["67010.5","1.5","0","3"]],"bids":[["67000.1","2","0","1"]],"ts":"1717200000000"}]}"#.to_vec(),
+            },
+        }]);
+
+        let request = GetOrderBookRequest {
+            instrument_id: "BTC-USD".to_string(),
+            depth: Some(1),
+        };
+
+        let orderbooks = block_on(
+            ApiClient::new(Exchange::Okx)
+                .call::<ApiResponse<Vec<OrderBook>>, GetOrderBookRequest, OkxAuth>(request, None),
+        )
+        .unwrap();
+
+        assert_eq!(orderbooks[0].asks[0].price, 67010.5);
+        assert_eq!(orderbooks[0].bids[0].size, 2.0);
+
+        // every recorded call is served once
+        assert!(recorded_traffic().is_empty());
+    }
+
+    #[test]
+    fn test_only_reads_go_out_directly() {
+        let orderbook = ApiClient::http_request::<_, OkxAuth>(
+            &GetOrderBookRequest {
+                instrument_id: "BTC-USD".to_string(),
+                depth: Some(1),
+            },
+            None,
+        );
+        // how the api client sends coinbase and binance cancels
+        let cancel = CanisterHttpRequestArgument {
+            method: HttpMethod::POST,
+            headers: vec![HttpHeader {
+                name: "X-HTTP-Method-Override".to_string(),
+                value: "DELETE".to_string(),
+            }],
+            ..orderbook.clone()
+        };
+
+        assert!(is_read_only(&orderbook));
+        assert!(!is_read_only(&cancel));
+        assert!(!is_read_only(&CanisterHttpRequestArgument {
+            method: HttpMethod::GET,
+            ..cancel.clone()
+        }));
+
+        // overridden methods are refused until the proxy is known to honor them
+        assert!(matches!(
+            block_on(ProxyTransport.send(cancel.clone())),
+            Err(ApiClientErrors::UnsupportedMethod { method }) if method == "DELETE"
+        ));
+        assert!(matches!(
+            block_on(OutcallTransport.send(cancel.clone())),
+            Err(ApiClientErrors::UnsupportedMethod { .. })
+        ));
+
+        Config::set_proxy_method_override(true);
+        assert!(check_method_override(&cancel).is_ok());
+    }
+
+    #[test]
+    fn test_transport_defaults_to_proxy() {
+        assert_eq!(TransportKind::get(Exchange::Kraken), TransportKind::Proxy);
+
+        TransportKind::set(Exchange::Kraken, TransportKind::Outcall);
+
+        assert_eq!(TransportKind::get(Exchange::Kraken), TransportKind::Outcall);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
     exchange::Exchange,
     memory::{Memory, MemoryLocation, MEMORY_MANAGER},
-    storable_wrapper::StorableWrapper,
+    signer::{SealedSecret, SigningConsent},
+    storable_wrapper::{StorableWrapper, Versioned},
 };
 use candid::{CandidType, Principal};
 use ic_stable_structures::StableBTreeMap;
@@ -12,6 +13,7 @@ type ApiId = u32;
 type ApiKey = String;
 type UserApiKeysTable = StableBTreeMap<(Principal, ApiId), StorableWrapper<ApiData>, Memory>;
 type ApiIndexes = StableBTreeMap<ApiKey, ApiId, Memory>;
+type SigningKeysTable = StableBTreeMap<(Principal, ApiId), StorableWrapper<SigningKey>, Memory>;
 
 thread_local! {
   static API_KEYS_INDEX: RefCell<ApiIndexes> =  RefCell::new(
@@ -25,6 +27,12 @@ thread_local! {
           MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::UserKeys.memory_id())),
       )
   );
+
+  static SIGNING_KEYS: RefCell<SigningKeysTable> = RefCell::new(
+      StableBTreeMap::init(
+          MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::SigningKeys.memory_id())),
+      )
+  );
 }
 
 #[derive(Serialize, Deserialize, CandidType, Clone, Debug, PartialEq, Eq)]
@@ -34,6 +42,17 @@ pub struct ApiData {
     pub passphrase: Option<String>,
 }
 
+impl Versioned for ApiData {}
+
+/// secret of an api key the owner opted in to canister signing with
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SigningKey {
+    pub secret: SealedSecret,
+    pub consent: SigningConsent,
+}
+
+impl Versioned for SigningKey {}
+
 pub struct ApiStore {}
 
 impl ApiStore {
@@ -67,6 +86,8 @@ impl ApiStore {
     pub fn remove_key(identity: &Principal, api_key: &String) -> Option<ApiData> {
         let id = Self::get_id(api_key)?;
 
+        SIGNING_KEYS.with_borrow_mut(|k| k.remove(&(*identity, id)));
+
         USER_API_KEYS.with_borrow_mut(|k| k.remove(&(*identity, id)).as_deref().cloned())
     }
 
@@ -75,6 +96,58 @@ impl ApiStore {
 
         USER_API_KEYS.with_borrow(|k| k.get(&(*identity, id)).as_deref().cloned())
     }
+
+    /// id of the api key when it is registered by the principal
+    fn get_own_id(identity: &Principal, api_key: &String) -> Option<ApiId> {
+        let id = Self::get_id(api_key)?;
+
+        USER_API_KEYS
+            .with_borrow(|k| k.contains_key(&(*identity, id)))
+            .then_some(id)
+    }
+
+    /// opts the api key in to canister signing, replacing previously stored secret
+    pub fn store_secret(
+        identity: &Principal,
+        api_key: &String,
+        secret: SealedSecret,
+        consent: SigningConsent,
+    ) -> Option<()> {
+        let id = Self::get_own_id(identity, api_key)?;
+
+        SIGNING_KEYS.with_borrow_mut(|k| {
+            k.insert(
+                (*identity, id),
+                StorableWrapper(SigningKey { secret, consent }),
+            )
+        });
+
+        Some(())
+    }
+
+    pub fn set_consent(
+        identity: &Principal,
+        api_key: &String,
+        consent: SigningConsent,
+    ) -> Option<()> {
+        let mut key = Self::get_signing_key(identity, api_key)?;
+        key.consent = consent;
+
+        Self::store_secret(identity, api_key, key.secret, key.consent)
+    }
+
+    /// opts the api key out of canister signing
+    pub fn remove_secret(identity: &Principal, api_key: &String) -> bool {
+        Self::get_own_id(identity, api_key)
+            .and_then(|id| SIGNING_KEYS.with_borrow_mut(|k| k.remove(&(*identity, id))))
+            .is_some()
+    }
+
+    pub fn get_signing_key(identity: &Principal, api_key: &String) -> Option<SigningKey> {
+        let id = Self::get_own_id(identity, api_key)?;
+
+        SIGNING_KEYS.with_borrow(|k| k.get(&(*identity, id)).map(|k| k.0))
+    }
 }
 
 #[cfg(test)]
@@ -131,4 +204,48 @@ mod tests {
             second_data
         );
     }
+
+    #[test]
+    fn test_signing_key() {
+        let principal = Principal::from_text("aaaaa-aa").unwrap();
+        let other = Principal::anonymous();
+
+        let data = ApiData {
+            exchange: Exchange::Okx,
+            api_key: "signing_key".to_string(),
+            passphrase: Some("passphrase".to_string()),
+        };
+        let secret = SealedSecret::seal(&[1u8; 32], &[2u8; 16], "secret").unwrap();
+        let consent = SigningConsent {
+            read: true,
+            trade: false,
+        };
+
+        // only the owner of a registered key can store its secret
+        assert!(
+            ApiStore::store_secret(&principal, &data.api_key, secret.clone(), consent).is_none()
+        );
+        let id = ApiStore::register_key(&principal, data.clone()).unwrap();
+        assert!(ApiStore::store_secret(&other, &data.api_key, secret.clone(), consent).is_none());
+        assert!(
+            ApiStore::store_secret(&principal, &data.api_key, secret.clone(), consent).is_some()
+        );
+
+        let trade = SigningConsent {
+            read: true,
+            trade: true,
+        };
+        ApiStore::set_consent(&principal, &data.api_key, trade).unwrap();
+        assert_eq!(
+            ApiStore::get_signing_key(&principal, &data.api_key).unwrap(),
+            SigningKey {
+                secret,
+                consent: trade
+            }
+        );
+
+        // removing the api key drops its secret as well
+        ApiStore::remove_key(&principal, &data.api_key);
+        assert!(SIGNING_KEYS.with_borrow(|k| !k.contains_key(&(principal, id))));
+    }
 }
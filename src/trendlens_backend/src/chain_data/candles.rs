@@ -1,6 +1,7 @@
 use std::ops::{Deref, DerefMut};
 
 use super::{BTreeMap, Candle, Deserialize, Serialize, Timestamp, TimestampBased};
+use crate::interval::Interval;
 
 #[derive(Deserialize, Serialize, Default)]
 pub struct CandlesStore(BTreeMap<Timestamp, Candle>);
@@ -11,6 +12,68 @@ impl CandlesStore {
             self.insert(c.timestamp, c);
         }
     }
+
+    /// rolls stored candles up into buckets of given interval, buckets are aligned
+    /// to the interval so the first one may start before the range, minutes missing
+    /// in the store are skipped and buckets without any candle are not returned,
+    /// buckets still forming past the range or the last stored minute are left out
+    pub fn aggregate(&self, interval: Interval, range: std::ops::Range<Timestamp>) -> Vec<Candle> {
+        let seconds = interval.seconds();
+        let start = range.start - range.start % seconds;
+        let complete_until = match self.last_timestamp() {
+            Some(last) => range.end.min(last + Interval::OneMinute.seconds()),
+            None => range.end,
+        };
+
+        let mut buckets: Vec<Candle> = vec![];
+
+        for candle in self.get_between(start..range.end) {
+            let bucket_timestamp = candle.timestamp - candle.timestamp % seconds;
+
+            match buckets.last_mut() {
+                Some(bucket) if bucket.timestamp == bucket_timestamp => {
+                    bucket.highest_price = bucket.highest_price.max(candle.highest_price);
+                    bucket.lowest_price = bucket.lowest_price.min(candle.lowest_price);
+                    bucket.close_price = candle.close_price;
+                    bucket.volume += candle.volume;
+                }
+                _ => buckets.push(Candle {
+                    timestamp: bucket_timestamp,
+                    ..candle
+                }),
+            }
+        }
+
+        buckets.retain(|bucket| bucket.timestamp + seconds <= complete_until);
+
+        buckets
+    }
+
+    /// finds ranges inside given range where candles of given interval are missing,
+    /// candles are expected at timestamps aligned to the interval
+    pub fn find_gaps(
+        &self,
+        interval: Interval,
+        range: std::ops::Range<Timestamp>,
+    ) -> Vec<std::ops::Range<Timestamp>> {
+        let seconds = interval.seconds();
+        let mut expected = range.start - range.start % seconds;
+        let mut gaps = vec![];
+
+        for timestamp in self.range(expected..range.end).map(|(t, _)| *t) {
+            if timestamp > expected {
+                gaps.push(expected..timestamp);
+            }
+
+            expected = timestamp + seconds;
+        }
+
+        if expected < range.end {
+            gaps.push(expected..range.end);
+        }
+
+        gaps
+    }
 }
 
 impl Deref for CandlesStore {
@@ -43,3 +106,102 @@ impl TimestampBased for CandlesStore {
             .collect::<Vec<_>>()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: u64, open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            timestamp,
+            open_price: open,
+            highest_price: high,
+            lowest_price: low,
+            close_price: close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut store = CandlesStore::default();
+        store.insert_many(vec![
+            candle(600, 1.0, 2.0, 0.5, 1.5),
+            candle(660, 1.5, 3.0, 1.0, 2.5),
+            // 720 is missing
+            candle(780, 2.5, 2.6, 0.2, 2.0),
+            candle(840, 2.0, 2.1, 1.9, 2.1),
+            // next bucket is still forming
+            candle(900, 2.1, 2.2, 2.0, 2.2),
+        ]);
+
+        let candles = store.aggregate(Interval::FiveMinutes, 650..1000);
+
+        assert_eq!(
+            candles,
+            vec![Candle {
+                timestamp: 600,
+                open_price: 1.0,
+                highest_price: 3.0,
+                lowest_price: 0.2,
+                close_price: 2.1,
+                volume: 4.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_aggregate_drops_buckets_past_range() {
+        let mut store = CandlesStore::default();
+        store.insert_many(
+            (0..10)
+                .map(|i| candle(i * 60, 1.0, 1.0, 1.0, 1.0))
+                .collect(),
+        );
+
+        let timestamps = |range| {
+            store
+                .aggregate(Interval::FiveMinutes, range)
+                .iter()
+                .map(|c| c.timestamp)
+                .collect::<Vec<_>>()
+        };
+
+        // the second bucket is complete in the store, but cut by the range
+        assert_eq!(timestamps(0..540), vec![0]);
+        assert_eq!(timestamps(0..600), vec![0, 300]);
+    }
+
+    #[test]
+    fn test_find_gaps() {
+        let mut store = CandlesStore::default();
+        store.insert_many(vec![
+            candle(120, 1.0, 1.0, 1.0, 1.0),
+            candle(180, 1.0, 1.0, 1.0, 1.0),
+            candle(360, 1.0, 1.0, 1.0, 1.0),
+        ]);
+
+        assert_eq!(
+            store.find_gaps(Interval::OneMinute, 30..600),
+            vec![0..120, 240..360, 420..600]
+        );
+        assert_eq!(store.find_gaps(Interval::OneMinute, 120..240), vec![]);
+    }
+
+    #[test]
+    fn test_aggregate_skips_empty_buckets() {
+        let mut store = CandlesStore::default();
+        store.insert_many(vec![
+            candle(0, 1.0, 1.0, 1.0, 1.0),
+            candle(7200, 2.0, 2.0, 2.0, 2.0),
+            candle(10800, 3.0, 3.0, 3.0, 3.0),
+        ]);
+
+        let candles = store.aggregate(Interval::OneHour, 0..10800);
+
+        assert_eq!(
+            candles.iter().map(|c| c.timestamp).collect::<Vec<_>>(),
+            vec![0, 7200]
+        );
+    }
+}
@@ -2,9 +2,10 @@ use std::{cell::RefCell, collections::BTreeMap};
 
 use crate::{
     exchange::{Candle, Exchange},
+    interval::Interval,
     memory::{Memory, MemoryLocation, MEMORY_MANAGER},
     pair::Pair,
-    storable_wrapper::StorableWrapper,
+    storable_wrapper::{StorableWrapper, Versioned},
 };
 use ic_stable_structures::StableBTreeMap;
 use serde::{Deserialize, Serialize};
@@ -13,12 +14,13 @@ mod candles;
 use candles::CandlesStore;
 
 type Timestamp = u64;
-type ExchangeStore = StableBTreeMap<(Exchange, Pair), StorableWrapper<ExchangeData>, Memory>;
+type ExchangeStore =
+    StableBTreeMap<(Exchange, Pair, Interval), StorableWrapper<ExchangeData>, Memory>;
 
 thread_local! {
     static EXCHANGE_STORE: RefCell<ExchangeStore> = RefCell::new(
         StableBTreeMap::init(
-            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Exchanges.memory_id())),
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Candles.memory_id())),
         )
     );
 }
@@ -28,6 +30,8 @@ pub struct ExchangeData {
     pub candles: CandlesStore,
 }
 
+impl Versioned for ExchangeData {}
+
 pub trait TimestampBased {
     type Item: for<'de> Deserialize<'de> + Serialize;
 
@@ -38,11 +42,11 @@ pub trait TimestampBased {
 pub trait ChainData {
     fn key(&self) -> Exchange;
 
-    fn get_data(&self, pair: Pair) -> Option<StorableWrapper<ExchangeData>> {
-        EXCHANGE_STORE.with_borrow(|b| b.get(&(self.key(), pair)))
+    fn get_data(&self, pair: Pair, interval: Interval) -> Option<StorableWrapper<ExchangeData>> {
+        EXCHANGE_STORE.with_borrow(|b| b.get(&(self.key(), pair, interval)))
     }
 
-    fn set_data(&self, pair: Pair, data: StorableWrapper<ExchangeData>) {
-        EXCHANGE_STORE.with_borrow_mut(|b| b.insert((self.key(), pair), data));
+    fn set_data(&self, pair: Pair, interval: Interval, data: StorableWrapper<ExchangeData>) {
+        EXCHANGE_STORE.with_borrow_mut(|b| b.insert((self.key(), pair, interval), data));
     }
 }
@@ -0,0 +1,182 @@
+use crate::{
+    memory::{Memory, MemoryLocation, MEMORY_MANAGER},
+    storable_wrapper::{StorableWrapper, Versioned},
+};
+use candid::{CandidType, Principal};
+use ic_stable_structures::StableCell;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+/// idempotent proxy deployed on mainnet
+const DEFAULT_PROXY_CANISTER_ID: &str = "ahdfa-wyaaa-aaaal-ajpba-cai";
+
+/// seconds, okx rejects requests with timestamps older than 30 seconds
+const DEFAULT_MAX_TIMESTAMP_SKEW: u64 = 30;
+
+thread_local! {
+    static CONFIG: RefCell<StableCell<StorableWrapper<Config>, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Config.memory_id())),
+            StorableWrapper(Config::default()),
+        )
+        .expect("failed to init config")
+    );
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Config {
+    pub proxy_canister_id: Principal,
+    /// seconds a signature timestamp may differ from canister time
+    pub max_timestamp_skew: u64,
+    /// the proxy sends requests with the method of their `X-HTTP-Method-Override`
+    /// header, coinbase and binance cancels are DELETE requests outcalls cannot make
+    pub proxy_method_override: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            proxy_canister_id: Principal::from_text(DEFAULT_PROXY_CANISTER_ID).unwrap(),
+            max_timestamp_skew: DEFAULT_MAX_TIMESTAMP_SKEW,
+            proxy_method_override: false,
+        }
+    }
+}
+
+/// config stored before timestamp skew was configurable
+#[derive(Deserialize, Serialize)]
+struct ConfigV1 {
+    proxy_canister_id: Principal,
+}
+
+/// config stored before the proxy method override was configurable
+#[derive(Deserialize, Serialize)]
+struct ConfigV2 {
+    proxy_canister_id: Principal,
+    max_timestamp_skew: u64,
+}
+
+impl Versioned for Config {
+    const VERSION: u16 = 3;
+
+    fn migrate(version: u16, payload: &[u8]) -> Self {
+        match version {
+            1 => {
+                let v1: ConfigV1 = bincode::deserialize(payload).unwrap();
+
+                Config {
+                    proxy_canister_id: v1.proxy_canister_id,
+                    ..Config::default()
+                }
+            }
+            2 => {
+                let v2: ConfigV2 = bincode::deserialize(payload).unwrap();
+
+                Config {
+                    proxy_canister_id: v2.proxy_canister_id,
+                    max_timestamp_skew: v2.max_timestamp_skew,
+                    ..Config::default()
+                }
+            }
+            _ => panic!("unknown config version {}", version),
+        }
+    }
+}
+
+/// passed on install and upgrade, unset fields keep the stored values
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct InitArgs {
+    pub proxy_canister_id: Option<Principal>,
+    pub admins: Option<Vec<Principal>>,
+    pub max_timestamp_skew: Option<u64>,
+    pub proxy_method_override: Option<bool>,
+}
+
+impl Config {
+    pub fn get() -> Config {
+        CONFIG.with_borrow(|c| c.get().0.clone())
+    }
+
+    fn update(f: impl FnOnce(&mut Config)) {
+        let mut config = Self::get();
+        f(&mut config);
+
+        CONFIG
+            .with_borrow_mut(|c| c.set(StorableWrapper(config)))
+            .expect("failed to store config");
+    }
+
+    pub fn proxy_canister_id() -> Principal {
+        Self::get().proxy_canister_id
+    }
+
+    pub fn set_proxy_canister_id(proxy_canister_id: Principal) {
+        Self::update(|c| c.proxy_canister_id = proxy_canister_id);
+    }
+
+    pub fn max_timestamp_skew() -> u64 {
+        Self::get().max_timestamp_skew
+    }
+
+    pub fn set_max_timestamp_skew(max_timestamp_skew: u64) {
+        Self::update(|c| c.max_timestamp_skew = max_timestamp_skew);
+    }
+
+    pub fn proxy_method_override() -> bool {
+        Self::get().proxy_method_override
+    }
+
+    pub fn set_proxy_method_override(proxy_method_override: bool) {
+        Self::update(|c| c.proxy_method_override = proxy_method_override);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storable_wrapper::envelope;
+    use ic_stable_structures::Storable;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_proxy_canister_id() {
+        assert_eq!(
+            Config::proxy_canister_id(),
+            Principal::from_text(DEFAULT_PROXY_CANISTER_ID).unwrap()
+        );
+
+        let local_proxy = Principal::from_text("bkyz2-fmaaa-aaaaa-qaaaq-cai").unwrap();
+        Config::set_proxy_canister_id(local_proxy);
+
+        assert_eq!(Config::proxy_canister_id(), local_proxy);
+    }
+
+    #[test]
+    fn test_decode_v1_config() {
+        let local_proxy = Principal::from_text("bkyz2-fmaaa-aaaaa-qaaaq-cai").unwrap();
+        let v1 = ConfigV1 {
+            proxy_canister_id: local_proxy,
+        };
+
+        let bytes = envelope(1, &bincode::serialize(&v1).unwrap());
+        let decoded: StorableWrapper<Config> = StorableWrapper::from_bytes(Cow::Owned(bytes));
+
+        assert_eq!(decoded.proxy_canister_id, local_proxy);
+        assert_eq!(decoded.max_timestamp_skew, DEFAULT_MAX_TIMESTAMP_SKEW);
+    }
+
+    #[test]
+    fn test_decode_v2_config() {
+        let local_proxy = Principal::from_text("bkyz2-fmaaa-aaaaa-qaaaq-cai").unwrap();
+        let v2 = ConfigV2 {
+            proxy_canister_id: local_proxy,
+            max_timestamp_skew: 10,
+        };
+
+        let bytes = envelope(2, &bincode::serialize(&v2).unwrap());
+        let decoded: StorableWrapper<Config> = StorableWrapper::from_bytes(Cow::Owned(bytes));
+
+        assert_eq!(decoded.max_timestamp_skew, 10);
+        assert!(!decoded.proxy_method_override);
+    }
+}
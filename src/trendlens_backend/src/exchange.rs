@@ -1,24 +1,19 @@
 use candid::CandidType;
+use core::panic;
 use ic_stable_structures::{storable::Bound, Storable};
 use serde::{Deserialize, Serialize};
-use core::panic;
 use std::borrow::Cow;
 
 use crate::{
     chain_data::{ChainData, ExchangeData},
     instruments::get_instruments,
+    interval::Interval,
     pair::Pair,
     remote_exchanges::{
-        coinbase::{
-            Coinbase, CoinbaseOrdersRequest, GetProfileAccountsRequest, PostOrderBody, Statuses,
-        },
-        okx::{
-            api::{
-                GetBalanceRequest, GetInstrumentsRequest, InstrumentType, OrdersHistoryRequest,
-                PendingOrdersRequest as OxkPendingOrdersRequest, PlaceOrderBody,
-            },
-            Okx,
-        },
+        binance::Binance,
+        coinbase::{Coinbase, GetProfileAccountsRequest, SIGNATURE_SEPARATOR},
+        kraken::{Kraken, TEMPLATE_NONCE},
+        okx::{api::InstrumentType, Okx},
         request::{GeneralInstrumentsRequest, OrderSide},
         response::Instrument,
         ExchangeErrors, OpenData,
@@ -27,6 +22,9 @@ use crate::{
     storable_wrapper::StorableWrapper,
 };
 
+#[cfg(test)]
+use crate::remote_exchanges::mock::Mock;
+
 #[repr(u8)]
 #[derive(
     Deserialize, Serialize, CandidType, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy,
@@ -34,6 +32,11 @@ use crate::{
 pub enum Exchange {
     Okx,
     Coinbase,
+    Binance,
+    Kraken,
+    /// serves fixtures without outcalls, only built for tests
+    #[cfg(test)]
+    Mock,
 }
 
 impl From<Exchange> for u8 {
@@ -47,6 +50,10 @@ impl From<u8> for Exchange {
         match value {
             0 => Exchange::Okx,
             1 => Exchange::Coinbase,
+            2 => Exchange::Binance,
+            3 => Exchange::Kraken,
+            #[cfg(test)]
+            4 => Exchange::Mock,
             _ => panic!("Invalid exchange type"),
         }
     }
@@ -63,7 +70,7 @@ impl Storable for Exchange {
     }
 
     fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
-        ic_cdk::println!("{:?}", *self as u8);
+        log!("{:?}", *self as u8);
         Cow::Owned(vec![(*self).into()])
     }
 }
@@ -71,6 +78,10 @@ impl Storable for Exchange {
 pub enum ExchangeImpl {
     Okx(Okx),
     Coinbase(Coinbase),
+    Binance(Binance),
+    Kraken(Kraken),
+    #[cfg(test)]
+    Mock(Mock),
 }
 
 pub trait ExchangeId {
@@ -103,6 +114,10 @@ impl ExchangeImpl {
         match exchange_type {
             Exchange::Coinbase => Self::Coinbase(Coinbase::default()),
             Exchange::Okx => Self::Okx(Okx::default()),
+            Exchange::Binance => Self::Binance(Binance::default()),
+            Exchange::Kraken => Self::Kraken(Kraken::default()),
+            #[cfg(test)]
+            Exchange::Mock => Self::Mock(Mock),
         }
     }
 
@@ -110,6 +125,10 @@ impl ExchangeImpl {
         match self {
             ExchangeImpl::Coinbase(c) => c.get_pairs(instrument_type),
             ExchangeImpl::Okx(o) => o.get_pairs(instrument_type),
+            ExchangeImpl::Binance(b) => b.get_pairs(instrument_type),
+            ExchangeImpl::Kraken(k) => k.get_pairs(instrument_type),
+            #[cfg(test)]
+            ExchangeImpl::Mock(m) => m.get_pairs(instrument_type),
         }
     }
 
@@ -119,161 +138,167 @@ impl ExchangeImpl {
         order_side: &OrderSide,
         price_limit: u32,
     ) -> f64 {
-        match self {
-            ExchangeImpl::Coinbase(c) => {
-                let orderbook = c
-                    .get_orderbook(pair, 50)
-                    .await
-                    .expect("failed to get orderbook data");
-
-                let orders = match order_side {
-                    OrderSide::Buy => &orderbook.asks,
-                    OrderSide::Sell => &orderbook.bids,
-                };
-
-                let start_price = orders.first().expect("no orders").price;
-                let stop_price = start_price * (1.0 + price_limit as f64 / 100.0);
-
-                orders
-                    .iter()
-                    .filter_map(|order| {
-                        if order.price <= stop_price {
-                            Some(order.size)
-                        } else {
-                            None
-                        }
-                    })
-                    .fold(0.0, |acc, x| acc + x)
-            }
-            ExchangeImpl::Okx(o) => {
-                let orderbook = o
-                    .get_orderbook(pair, 50)
-                    .await
-                    .expect("failed to get orderbook data");
-
-                let orders = match order_side {
-                    OrderSide::Buy => &orderbook.asks,
-                    OrderSide::Sell => &orderbook.bids,
-                };
-
-                let start_price = orders.first().expect("no orders").price;
-                let stop_price = start_price * (1.0 + price_limit as f64 / 100.0);
-
-                orders
-                    .iter()
-                    .filter_map(|order| {
-                        if order.price <= stop_price {
-                            Some(order.size)
-                        } else {
-                            None
-                        }
-                    })
-                    .fold(0.0, |acc, x| acc + x)
-            }
+        let orderbook = match self {
+            ExchangeImpl::Coinbase(c) => c.get_orderbook(pair, 50).await,
+            ExchangeImpl::Okx(o) => o.get_orderbook(pair, 50).await,
+            ExchangeImpl::Binance(b) => b.get_orderbook(pair, 50).await,
+            ExchangeImpl::Kraken(k) => k.get_orderbook(pair, 50).await,
+            #[cfg(test)]
+            ExchangeImpl::Mock(m) => m.get_orderbook(pair, 50).await,
         }
+        .expect("failed to get orderbook data");
+
+        let orders = match order_side {
+            OrderSide::Buy => &orderbook.asks,
+            OrderSide::Sell => &orderbook.bids,
+        };
+
+        let start_price = orders.first().expect("no orders").price;
+        let stop_price = start_price * (1.0 + price_limit as f64 / 100.0);
+
+        orders
+            .iter()
+            .filter_map(|order| {
+                if order.price <= stop_price {
+                    Some(order.size)
+                } else {
+                    None
+                }
+            })
+            .fold(0.0, |acc, x| acc + x)
     }
 
-    // right now for testing purposes used single request, but it should be
-    // preconstructed for further use or migrate signature generation to client
-    pub fn get_signature_string(&self, request: &Request) -> String {
+    /// pre-sign string of the request, exchange requests come from the same
+    /// builders user data implementations send. `None` when the request is
+    /// sent without signature or cannot be sent at all
+    pub fn get_signature_string(&self, request: &Request) -> Option<String> {
         match self {
             ExchangeImpl::Coinbase(c) => match request {
-                Request::OrdersList(i) => {
-                    let request = CoinbaseOrdersRequest {
-                        product_id: Some(i.instrument_id.to_string()),
-                        market_type: Some(i.instrument_type.to_string()),
-                        limit: 100,
-                        status: Some(match i.pending {
-                            true => Statuses(vec!["open".to_string(), "pending".to_string()]),
-                            false => Statuses(vec!["done".to_string(), "rejected".to_string()]),
-                        }),
-                    };
-
-                    c.get_signature_data(request)
+                Request::OrdersList(i) => Some(c.get_signature_data(Coinbase::orders_request(i))),
+                Request::Balances(_) => Some(c.get_signature_data(GetProfileAccountsRequest {})),
+                Request::PostOrder(request) => {
+                    Some(c.get_signature_data(Coinbase::post_order_request(request)))
                 }
-                Request::Balances(_) => {
-                    let request = GetProfileAccountsRequest {};
-
-                    c.get_signature_data(request)
+                Request::CancelOrder(request) => {
+                    Some(c.get_signature_data(Coinbase::cancel_order_request(request)))
                 }
-                Request::PostOrder(request) => {
-                    let exchange_request = PostOrderBody {
-                        product_id: request.instrument_id.to_string(),
-                        price: request.order_price,
-                        size: match request.side {
-                            OrderSide::Buy => None,
-                            OrderSide::Sell => Some(request.size),
-                        },
-                        side: request.side.into(),
-                        funds: match request.side {
-                            OrderSide::Buy => Some(request.size),
-                            OrderSide::Sell => None,
-                        },
-                        order_type: request.order_type.into(),
-                    };
-
-                    c.get_signature_data(exchange_request)
+                Request::AmendOrder(request) => {
+                    let (cancel_request, post_request) = Coinbase::amend_order_requests(request);
+
+                    // emulated amendment executes two requests, each one is signed
+                    Some(format!(
+                        "{}{}{}",
+                        c.get_signature_data(cancel_request),
+                        SIGNATURE_SEPARATOR,
+                        c.get_signature_data(post_request)
+                    ))
+                }
+                Request::OrderDetails(request) => {
+                    Some(c.get_signature_data(Coinbase::order_details_request(request)))
+                }
+                Request::Fills(request) => {
+                    Some(c.get_signature_data(Coinbase::fills_request(request)))
                 }
-                _ => "".to_string(),
+                // answered without a request, nothing to sign
+                Request::Instruments(_) => None,
+                // rejected on execution
+                Request::Empty => None,
             },
             ExchangeImpl::Okx(o) => match request {
                 Request::OrdersList(i) => match i.pending {
-                    true => {
-                        let r = OxkPendingOrdersRequest {
-                            instrument_id: Some(i.instrument_id.to_string()),
-                            instrument_type: Some(i.instrument_type),
-                        };
-
-                        o.get_signature_data(r)
-                    }
+                    true => Some(o.get_signature_data(Okx::pending_orders_request(i))),
+                    false => Some(o.get_signature_data(Okx::orders_history_request(i))),
+                },
+                Request::Instruments(i) => Some(o.get_signature_data(Okx::instruments_request(i))),
+                Request::Balances(b) => Some(o.get_signature_data(Okx::balance_request(b))),
+                Request::PostOrder(request) => {
+                    Some(o.get_signature_data(Okx::place_order_request(request)))
+                }
+                Request::CancelOrder(request) => {
+                    Some(o.get_signature_data(Okx::cancel_order_request(request)))
+                }
+                Request::AmendOrder(request) => {
+                    Some(o.get_signature_data(Okx::amend_order_request(request)))
+                }
+                Request::OrderDetails(request) => {
+                    Some(o.get_signature_data(Okx::order_details_request(request)))
+                }
+                Request::Fills(request) => Some(o.get_signature_data(Okx::fills_request(request))),
+                Request::Empty => None,
+            },
+            ExchangeImpl::Binance(b) => match request {
+                Request::OrdersList(i) => match i.pending {
+                    true => Some(b.get_signature_data(Binance::open_orders_request(i))),
+                    false => Some(b.get_signature_data(Binance::all_orders_request(i))),
+                },
+                Request::Balances(_) => Some(b.get_signature_data(Binance::account_request())),
+                Request::PostOrder(request) => {
+                    Some(b.get_signature_data(Binance::new_order_request(request)))
+                }
+                Request::CancelOrder(request) => {
+                    Some(b.get_signature_data(Binance::cancel_order_request(request)))
+                }
+                Request::AmendOrder(request) => {
+                    Some(b.get_signature_data(Binance::cancel_replace_request(request)))
+                }
+                Request::OrderDetails(request) => {
+                    Some(b.get_signature_data(Binance::order_details_request(request)))
+                }
+                Request::Fills(request) => {
+                    Some(b.get_signature_data(Binance::fills_request(request)))
+                }
+                // public instruments are fetched without signature
+                Request::Instruments(_) => None,
+                Request::Empty => None,
+            },
+            // nonce is filled in when the leg is signed for execution
+            ExchangeImpl::Kraken(k) => match request {
+                Request::OrdersList(i) => match i.pending {
+                    true => Some(k.get_signature_data(Kraken::open_orders_request(TEMPLATE_NONCE))),
                     false => {
-                        let r = OrdersHistoryRequest {
-                            instrument_id: Some(i.instrument_id.to_string()),
-                            instrument_type: i.instrument_type,
-                        };
-
-                        o.get_signature_data(r)
+                        Some(k.get_signature_data(Kraken::closed_orders_request(TEMPLATE_NONCE)))
                     }
                 },
-                Request::Instruments(i) => {
-                    let request = GetInstrumentsRequest {
-                        instrument_id: i
-                            .instrument_id
-                            .as_ref()
-                            .and_then(|p| Okx::instrument_id(&p)),
-                        instrument_type: i.instrument_type,
-                    };
-                    o.get_signature_data(request)
+                Request::Balances(_) => {
+                    Some(k.get_signature_data(Kraken::balance_request(TEMPLATE_NONCE)))
                 }
-                Request::Balances(b) => {
-                    let request = GetBalanceRequest {
-                        currencies: b.currency.as_ref().and_then(|c| Some(c.join(","))),
-                    };
-
-                    o.get_signature_data(request)
+                // unsupported orders are rejected when the instruction is executed
+                Request::PostOrder(request) => Kraken::add_order_request(TEMPLATE_NONCE, request)
+                    .ok()
+                    .map(|r| k.get_signature_data(r)),
+                Request::CancelOrder(request) => Some(
+                    k.get_signature_data(Kraken::cancel_order_request(TEMPLATE_NONCE, request)),
+                ),
+                Request::AmendOrder(request) => {
+                    Some(k.get_signature_data(Kraken::amend_order_request(TEMPLATE_NONCE, request)))
                 }
-                Request::PostOrder(request) => {
-                    let exchange_request = PlaceOrderBody {
-                        side: Okx::side_string(request.side),
-                        instrument_id: request.instrument_id.to_string(),
-                        order_type: Okx::order_type_string(request.order_type),
-                        size: request.size.to_string(),
-                        order_price: request.order_price.map(|p| p.to_string()),
-                        trade_mode: Okx::trade_mode_string(request.trade_mode),
-                        ..Default::default()
-                    };
-
-                    o.get_signature_data(exchange_request)
+                Request::OrderDetails(request) => Some(
+                    k.get_signature_data(Kraken::order_details_request(TEMPLATE_NONCE, request)),
+                ),
+                Request::Fills(_) => {
+                    Some(k.get_signature_data(Kraken::fills_request(TEMPLATE_NONCE)))
                 }
-                _ => "".to_string(),
+                // public instruments are fetched without signature
+                Request::Instruments(_) | Request::Empty => None,
             },
+            // fixtures are served without authentication
+            #[cfg(test)]
+            ExchangeImpl::Mock(_) => None,
         }
     }
 
-    pub fn get_data(&self, pair: Pair) -> Option<StorableWrapper<ExchangeData>> {
+    pub fn get_data(
+        &self,
+        pair: Pair,
+        interval: Interval,
+    ) -> Option<StorableWrapper<ExchangeData>> {
         match self {
-            ExchangeImpl::Coinbase(c) => c.get_data(pair),
-            ExchangeImpl::Okx(o) => o.get_data(pair),
+            ExchangeImpl::Coinbase(c) => c.get_data(pair, interval),
+            ExchangeImpl::Okx(o) => o.get_data(pair, interval),
+            ExchangeImpl::Binance(b) => b.get_data(pair, interval),
+            ExchangeImpl::Kraken(k) => k.get_data(pair, interval),
+            #[cfg(test)]
+            ExchangeImpl::Mock(m) => m.get_data(pair, interval),
         }
     }
 
@@ -285,13 +310,21 @@ impl ExchangeImpl {
         match self {
             ExchangeImpl::Coinbase(c) => c.get_taker_volume(pair, range).await,
             ExchangeImpl::Okx(o) => o.get_taker_volume(pair, range).await,
+            ExchangeImpl::Binance(b) => b.get_taker_volume(pair, range).await,
+            ExchangeImpl::Kraken(k) => k.get_taker_volume(pair, range).await,
+            #[cfg(test)]
+            ExchangeImpl::Mock(m) => m.get_taker_volume(pair, range).await,
         }
     }
 
-    pub fn set_data(&self, pair: Pair, data: StorableWrapper<ExchangeData>) {
+    pub fn set_data(&self, pair: Pair, interval: Interval, data: StorableWrapper<ExchangeData>) {
         match self {
-            ExchangeImpl::Coinbase(c) => c.set_data(pair, data),
-            ExchangeImpl::Okx(o) => o.set_data(pair, data),
+            ExchangeImpl::Coinbase(c) => c.set_data(pair, interval, data),
+            ExchangeImpl::Okx(o) => o.set_data(pair, interval, data),
+            ExchangeImpl::Binance(b) => b.set_data(pair, interval, data),
+            ExchangeImpl::Kraken(k) => k.set_data(pair, interval, data),
+            #[cfg(test)]
+            ExchangeImpl::Mock(m) => m.set_data(pair, interval, data),
         }
     }
 
@@ -307,6 +340,10 @@ impl ExchangeImpl {
         match self {
             ExchangeImpl::Coinbase(c) => c.get_public_instruments(get_instruments_request).await,
             ExchangeImpl::Okx(o) => o.get_public_instruments(get_instruments_request).await,
+            ExchangeImpl::Binance(b) => b.get_public_instruments(get_instruments_request).await,
+            ExchangeImpl::Kraken(k) => k.get_public_instruments(get_instruments_request).await,
+            #[cfg(test)]
+            ExchangeImpl::Mock(m) => m.get_public_instruments(get_instruments_request).await,
         }
     }
 
@@ -314,11 +351,15 @@ impl ExchangeImpl {
         &self,
         pair: &Pair,
         range: std::ops::Range<u64>,
-        interval: u32,
+        interval: Interval,
     ) -> Result<Vec<Candle>, super::ExchangeErrors> {
         match self {
             ExchangeImpl::Coinbase(c) => c.fetch_candles(pair, range, interval).await,
-            ExchangeImpl::Okx(o) => o.fetch_candles(pair, range, interval).await
+            ExchangeImpl::Okx(o) => o.fetch_candles(pair, range, interval).await,
+            ExchangeImpl::Binance(b) => b.fetch_candles(pair, range, interval).await,
+            ExchangeImpl::Kraken(k) => k.fetch_candles(pair, range, interval).await,
+            #[cfg(test)]
+            ExchangeImpl::Mock(m) => m.fetch_candles(pair, range, interval).await,
         }
     }
 
@@ -326,7 +367,7 @@ impl ExchangeImpl {
         &self,
         pair: &Pair,
         range: std::ops::Range<u64>,
-        interval: u32,
+        interval: Interval,
     ) -> Result<Vec<Candle>, super::ExchangeErrors> {
         match self {
             ExchangeImpl::Okx(o) => o.fetch_index_candles(pair, range, interval).await,
@@ -342,11 +383,404 @@ pub struct Candle {
     pub highest_price: f64,
     pub lowest_price: f64,
     pub close_price: f64,
-    pub volume: f64
+    pub volume: f64,
 }
 
+/// taker volume of a pair, okx reports it for the base currency over all
+/// its spot pairs and it is kept only under the usdt pair
 #[derive(Deserialize, CandidType, Serialize, Clone, PartialEq, Debug)]
 pub struct TimeVolume {
     pub timestamp: u64,
     pub volume: f64,
+    /// taker side split, missing for exchanges which report total volume only
+    pub buy_volume: Option<f64>,
+    pub sell_volume: Option<f64>,
+    /// (buy - sell) / (buy + sell), ranges from -1 (only sells) to 1 (only buys)
+    pub imbalance: Option<f64>,
+}
+
+impl TimeVolume {
+    pub fn new(timestamp: u64, volume: f64) -> Self {
+        Self {
+            timestamp,
+            volume,
+            buy_volume: None,
+            sell_volume: None,
+            imbalance: None,
+        }
+    }
+
+    pub fn with_split(timestamp: u64, buy_volume: f64, sell_volume: f64) -> Self {
+        let volume = buy_volume + sell_volume;
+
+        Self {
+            timestamp,
+            volume,
+            buy_volume: Some(buy_volume),
+            sell_volume: Some(sell_volume),
+            imbalance: (volume > 0.0).then(|| (buy_volume - sell_volume) / volume),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api_client::ApiClient,
+        remote_exchanges::{
+            binance::BinanceAuth,
+            kraken::KrakenAuth,
+            okx::auth::OkxAuth,
+            request::{
+                GeneralAmendOrderRequest, GeneralBalanceRequest, GeneralCancelOrderRequest,
+                GeneralFillsRequest, GeneralOrderDetailsRequest, GeneralOrdersListRequest,
+                GeneralPostOrderRequest, OrderType, TradeMode,
+            },
+            ApiRequest,
+        },
+    };
+    use std::str::FromStr;
+
+    /// pre-sign string rebuilt from the http request the api client sends
+    fn sent<R: ApiRequest>(request: R) -> String {
+        let http = ApiClient::http_request::<_, OkxAuth>(&request, None);
+
+        let method = http
+            .headers
+            .iter()
+            .find(|h| h.name == "X-HTTP-Method-Override")
+            .map(|h| h.value.clone())
+            .unwrap_or_else(|| format!("{:?}", http.method));
+        let host = format!("https://{}", R::HOST);
+        let body = String::from_utf8(http.body.unwrap_or_default()).unwrap();
+
+        format!(
+            "{}{}{}",
+            method,
+            http.url.strip_prefix(&host).unwrap(),
+            body
+        )
+    }
+
+    fn pair() -> Pair {
+        Pair::from_str("btc-usd").unwrap()
+    }
+
+    fn instruments() -> GeneralInstrumentsRequest {
+        GeneralInstrumentsRequest {
+            instrument_type: InstrumentType::Spot,
+            instrument_id: Some(pair()),
+        }
+    }
+
+    fn balances() -> GeneralBalanceRequest {
+        GeneralBalanceRequest {
+            currency: Some(vec!["BTC".to_string(), "USD".to_string()]),
+        }
+    }
+
+    fn post_order(side: OrderSide) -> GeneralPostOrderRequest {
+        GeneralPostOrderRequest {
+            instrument_id: pair(),
+            trade_mode: TradeMode::Cash,
+            side,
+            margin_currency: None,
+            position_side: None,
+            order_type: OrderType::Limit,
+            size: 0.5,
+            order_price: Some(67000.5),
+        }
+    }
+
+    fn cancel_order() -> GeneralCancelOrderRequest {
+        GeneralCancelOrderRequest {
+            instrument_id: pair(),
+            order_id: "d0c5340b-6d6c-49d9-b567-48c4bfca13d2".to_string(),
+        }
+    }
+
+    fn amend_order() -> GeneralAmendOrderRequest {
+        GeneralAmendOrderRequest {
+            instrument_id: pair(),
+            order_id: "d0c5340b-6d6c-49d9-b567-48c4bfca13d2".to_string(),
+            side: OrderSide::Sell,
+            size: 0.25,
+            price: 68000.0,
+        }
+    }
+
+    fn order_details() -> GeneralOrderDetailsRequest {
+        GeneralOrderDetailsRequest {
+            instrument_id: pair(),
+            order_id: "d0c5340b-6d6c-49d9-b567-48c4bfca13d2".to_string(),
+        }
+    }
+
+    fn fills() -> GeneralFillsRequest {
+        GeneralFillsRequest {
+            instrument_id: pair(),
+            order_id: Some("d0c5340b-6d6c-49d9-b567-48c4bfca13d2".to_string()),
+        }
+    }
+
+    fn orders_list(pending: bool) -> GeneralOrdersListRequest {
+        GeneralOrdersListRequest {
+            instrument_type: InstrumentType::Spot,
+            instrument_id: pair(),
+            pending,
+            cursor: Some("42".to_string()),
+            limit: Some(10),
+        }
+    }
+
+    #[test]
+    fn test_okx_signature_matches_sent_request() {
+        let okx = ExchangeImpl::Okx(Okx::default());
+        let sign = |request: Request| okx.get_signature_string(&request).unwrap();
+
+        assert_eq!(
+            sign(Request::Instruments(instruments())),
+            sent(Okx::instruments_request(&instruments()))
+        );
+        assert_eq!(
+            sign(Request::Balances(balances())),
+            sent(Okx::balance_request(&balances()))
+        );
+        assert_eq!(
+            sign(Request::PostOrder(post_order(OrderSide::Buy))),
+            sent(Okx::place_order_request(&post_order(OrderSide::Buy)))
+        );
+        assert_eq!(
+            sign(Request::CancelOrder(cancel_order())),
+            sent(Okx::cancel_order_request(&cancel_order()))
+        );
+        assert_eq!(
+            sign(Request::AmendOrder(amend_order())),
+            sent(Okx::amend_order_request(&amend_order()))
+        );
+        assert_eq!(
+            sign(Request::OrderDetails(order_details())),
+            sent(Okx::order_details_request(&order_details()))
+        );
+        assert_eq!(
+            sign(Request::Fills(fills())),
+            sent(Okx::fills_request(&fills()))
+        );
+        assert_eq!(
+            sign(Request::OrdersList(orders_list(true))),
+            sent(Okx::pending_orders_request(&orders_list(true)))
+        );
+        assert_eq!(
+            sign(Request::OrdersList(orders_list(false))),
+            sent(Okx::orders_history_request(&orders_list(false)))
+        );
+    }
+
+    #[test]
+    fn test_coinbase_signature_matches_sent_request() {
+        let coinbase = ExchangeImpl::Coinbase(Coinbase::default());
+        let sign = |request: Request| coinbase.get_signature_string(&request).unwrap();
+
+        assert_eq!(
+            sign(Request::Balances(balances())),
+            sent(GetProfileAccountsRequest {})
+        );
+
+        for side in [OrderSide::Buy, OrderSide::Sell] {
+            assert_eq!(
+                sign(Request::PostOrder(post_order(side))),
+                sent(Coinbase::post_order_request(&post_order(side)))
+            );
+        }
+
+        assert_eq!(
+            sign(Request::CancelOrder(cancel_order())),
+            sent(Coinbase::cancel_order_request(&cancel_order()))
+        );
+
+        let (cancel_request, post_request) = Coinbase::amend_order_requests(&amend_order());
+        assert_eq!(
+            sign(Request::AmendOrder(amend_order())),
+            format!(
+                "{}{}{}",
+                sent(cancel_request),
+                SIGNATURE_SEPARATOR,
+                sent(post_request)
+            )
+        );
+
+        assert_eq!(
+            sign(Request::OrderDetails(order_details())),
+            sent(Coinbase::order_details_request(&order_details()))
+        );
+        assert_eq!(
+            sign(Request::Fills(fills())),
+            sent(Coinbase::fills_request(&fills()))
+        );
+
+        for pending in [true, false] {
+            assert_eq!(
+                sign(Request::OrdersList(orders_list(pending))),
+                sent(Coinbase::orders_request(&orders_list(pending)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_binance_signature_matches_sent_request() {
+        // query the api client sends, without the signature the auth appends
+        fn sent_query<R: ApiRequest>(request: R, auth: &BinanceAuth) -> String {
+            let http = ApiClient::http_request(&request, Some(auth));
+            let (_, query) = http.url.split_once('?').unwrap();
+            query.strip_suffix("&signature=sig").unwrap().to_string()
+        }
+
+        let binance = ExchangeImpl::Binance(Binance::default());
+        let timestamp = 1_717_200_000;
+        let auth = BinanceAuth {
+            api_key: "key".to_string(),
+            signature: "sig".to_string(),
+            timestamp: timestamp * 1000,
+        };
+        let signed = |request: Request| {
+            let payload = binance.get_signature_string(&request).unwrap();
+            format!("{}{}", payload, timestamp * 1000)
+        };
+
+        assert_eq!(
+            signed(Request::Balances(balances())),
+            sent_query(Binance::account_request(), &auth)
+        );
+        assert_eq!(
+            signed(Request::PostOrder(post_order(OrderSide::Buy))),
+            sent_query(
+                Binance::new_order_request(&post_order(OrderSide::Buy)),
+                &auth
+            )
+        );
+        assert_eq!(
+            signed(Request::CancelOrder(cancel_order())),
+            sent_query(Binance::cancel_order_request(&cancel_order()), &auth)
+        );
+        assert_eq!(
+            signed(Request::AmendOrder(amend_order())),
+            sent_query(Binance::cancel_replace_request(&amend_order()), &auth)
+        );
+        assert_eq!(
+            signed(Request::OrderDetails(order_details())),
+            sent_query(Binance::order_details_request(&order_details()), &auth)
+        );
+        assert_eq!(
+            signed(Request::Fills(fills())),
+            sent_query(Binance::fills_request(&fills()), &auth)
+        );
+        assert_eq!(
+            signed(Request::OrdersList(orders_list(true))),
+            sent_query(Binance::open_orders_request(&orders_list(true)), &auth)
+        );
+        assert_eq!(
+            signed(Request::OrdersList(orders_list(false))),
+            sent_query(Binance::all_orders_request(&orders_list(false)), &auth)
+        );
+    }
+
+    #[test]
+    fn test_kraken_signature_matches_sent_request() {
+        // path and postdata the api client sends, the nonce is signed in between
+        fn sent_postdata<R: ApiRequest>(request: R, nonce: u64) -> String {
+            let http = ApiClient::http_request::<_, KrakenAuth>(&request, None);
+            let path = http
+                .url
+                .strip_prefix(&format!("https://{}", R::HOST))
+                .unwrap();
+            let body = String::from_utf8(http.body.unwrap_or_default()).unwrap();
+
+            format!("{}{}{}", path, nonce, body)
+        }
+
+        let nonce = Kraken::execution_nonce(1_717_200_000_000, 1);
+        let signed = |request: Request| {
+            let signature_data = ExchangeImpl::Kraken(Kraken::default())
+                .get_signature_string(&request)
+                .unwrap();
+            Kraken::with_nonce(&signature_data, nonce)
+        };
+
+        assert_eq!(
+            signed(Request::Balances(balances())),
+            sent_postdata(Kraken::balance_request(nonce), nonce)
+        );
+        assert_eq!(
+            signed(Request::PostOrder(post_order(OrderSide::Buy))),
+            sent_postdata(
+                Kraken::add_order_request(nonce, &post_order(OrderSide::Buy)).unwrap(),
+                nonce
+            )
+        );
+        assert_eq!(
+            signed(Request::CancelOrder(cancel_order())),
+            sent_postdata(Kraken::cancel_order_request(nonce, &cancel_order()), nonce)
+        );
+        assert_eq!(
+            signed(Request::AmendOrder(amend_order())),
+            sent_postdata(Kraken::amend_order_request(nonce, &amend_order()), nonce)
+        );
+        assert_eq!(
+            signed(Request::OrderDetails(order_details())),
+            sent_postdata(
+                Kraken::order_details_request(nonce, &order_details()),
+                nonce
+            )
+        );
+        assert_eq!(
+            signed(Request::Fills(fills())),
+            sent_postdata(Kraken::fills_request(nonce), nonce)
+        );
+        assert_eq!(
+            signed(Request::OrdersList(orders_list(true))),
+            sent_postdata(Kraken::open_orders_request(nonce), nonce)
+        );
+        assert_eq!(
+            signed(Request::OrdersList(orders_list(false))),
+            sent_postdata(Kraken::closed_orders_request(nonce), nonce)
+        );
+    }
+
+    #[test]
+    fn test_coinbase_cancel_signs_overridden_method() {
+        let coinbase = ExchangeImpl::Coinbase(Coinbase::default());
+
+        assert_eq!(
+            coinbase
+                .get_signature_string(&Request::CancelOrder(cancel_order()))
+                .as_deref(),
+            Some("DELETE/orders/d0c5340b-6d6c-49d9-b567-48c4bfca13d2?product_id=btc-usd")
+        );
+    }
+
+    #[test]
+    fn test_unsigned_requests_have_no_signature() {
+        let exchanges = [
+            ExchangeImpl::Okx(Okx::default()),
+            ExchangeImpl::Coinbase(Coinbase::default()),
+            ExchangeImpl::Binance(Binance::default()),
+            ExchangeImpl::Kraken(Kraken::default()),
+        ];
+
+        for exchange in &exchanges {
+            assert_eq!(exchange.get_signature_string(&Request::Empty), None);
+        }
+
+        // public instruments are fetched without signature, okx signs them
+        for exchange in &exchanges[1..] {
+            assert_eq!(
+                exchange.get_signature_string(&Request::Instruments(instruments())),
+                None
+            );
+        }
+        assert!(exchanges[0]
+            .get_signature_string(&Request::Instruments(instruments()))
+            .is_some());
+    }
 }
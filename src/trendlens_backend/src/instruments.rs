@@ -2,7 +2,7 @@ use crate::{
     exchange::Exchange,
     memory::{Memory, MemoryLocation, MEMORY_MANAGER},
     remote_exchanges::{okx::api::InstrumentType, response::Instrument},
-    storable_wrapper::StorableWrapper,
+    storable_wrapper::{StorableWrapper, Versioned},
 };
 use ic_stable_structures::StableBTreeMap;
 use std::cell::RefCell;
@@ -10,6 +10,8 @@ use std::cell::RefCell;
 // right now vec for convenience, but it should be a map or separate tree entries
 type ExchangeInstruments = StableBTreeMap<(Exchange, InstrumentType), StorableWrapper<Vec<Instrument>>, Memory>;
 
+impl Versioned for Vec<Instrument> {}
+
 thread_local! {
   static EXCHANGE_INSTRUMENTS: RefCell<ExchangeInstruments> = RefCell::new(
     StableBTreeMap::init(
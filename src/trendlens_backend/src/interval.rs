@@ -0,0 +1,94 @@
+use crate::remote_exchanges::ExchangeErrors;
+use candid::CandidType;
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[repr(u8)]
+#[derive(
+    CandidType, Deserialize, Serialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash,
+)]
+pub enum Interval {
+    OneMinute,
+    FiveMinutes,
+    FifteenMinutes,
+    OneHour,
+    FourHours,
+    OneDay,
+}
+
+impl Interval {
+    pub const ALL: [Interval; 6] = [
+        Interval::OneMinute,
+        Interval::FiveMinutes,
+        Interval::FifteenMinutes,
+        Interval::OneHour,
+        Interval::FourHours,
+        Interval::OneDay,
+    ];
+
+    /// candle length in seconds
+    pub fn seconds(&self) -> u64 {
+        match self {
+            Interval::OneMinute => 60,
+            Interval::FiveMinutes => 300,
+            Interval::FifteenMinutes => 900,
+            Interval::OneHour => 3600,
+            Interval::FourHours => 14400,
+            Interval::OneDay => 86400,
+        }
+    }
+}
+
+impl TryFrom<u8> for Interval {
+    type Error = ExchangeErrors;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Interval::OneMinute),
+            1 => Ok(Interval::FiveMinutes),
+            2 => Ok(Interval::FifteenMinutes),
+            3 => Ok(Interval::OneHour),
+            4 => Ok(Interval::FourHours),
+            5 => Ok(Interval::OneDay),
+            _ => Err(ExchangeErrors::UnsupportedInterval),
+        }
+    }
+}
+
+impl Storable for Interval {
+    const BOUND: Bound = Bound::Bounded {
+        max_size: std::mem::size_of::<Interval>() as u32,
+        is_fixed_size: true,
+    };
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bytes.as_ref()[0]
+            .try_into()
+            .expect("stored interval is invalid")
+    }
+
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(vec![*self as u8])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storable_roundtrip() {
+        for interval in Interval::ALL {
+            assert_eq!(Interval::from_bytes(interval.to_bytes()), interval);
+        }
+    }
+
+    #[test]
+    fn test_invalid_interval() {
+        assert!(matches!(
+            Interval::try_from(6),
+            Err(ExchangeErrors::UnsupportedInterval)
+        ));
+    }
+}
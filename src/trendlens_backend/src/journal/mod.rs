@@ -0,0 +1,121 @@
+use crate::{
+    exchange::Exchange,
+    memory::{Memory, MemoryLocation, MEMORY_MANAGER},
+    request_store::request::Response,
+    storable_wrapper::{StorableWrapper, Versioned},
+};
+use candid::{CandidType, Principal};
+use ic_stable_structures::StableBTreeMap;
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, ops::Range};
+
+type TransactionId = u32;
+/// position of the instruction within its transaction
+type InstructionIndex = u32;
+type JournalTable = StableBTreeMap<
+    (Principal, TransactionId, InstructionIndex),
+    StorableWrapper<JournalEntry>,
+    Memory,
+>;
+
+thread_local! {
+    static JOURNAL: RefCell<JournalTable> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Journal.memory_id())),
+        )
+    );
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct JournalEntry {
+    pub exchange: Exchange,
+    pub order_id: Option<String>,
+    /// canister time in nanoseconds
+    pub timestamp: u64,
+    pub response: Response,
+}
+
+impl Versioned for JournalEntry {}
+
+/// append-only record of successfully executed instructions
+pub struct Journal;
+
+impl Journal {
+    /// instructions succeed only once, so an entry is never overwritten
+    pub fn append(
+        identity: &Principal,
+        transaction_id: TransactionId,
+        instruction: InstructionIndex,
+        exchange: Exchange,
+        timestamp: u64,
+        response: &Response,
+    ) {
+        let key = (*identity, transaction_id, instruction);
+
+        JOURNAL.with_borrow_mut(|j| {
+            if j.contains_key(&key) {
+                return;
+            }
+
+            j.insert(
+                key,
+                StorableWrapper(JournalEntry {
+                    exchange,
+                    order_id: response.order_id(),
+                    timestamp,
+                    response: response.clone(),
+                }),
+            );
+        });
+    }
+
+    /// entries of the principal executed within the time range
+    pub fn get_entries(
+        identity: &Principal,
+        range: Range<u64>,
+    ) -> Vec<(TransactionId, InstructionIndex, JournalEntry)> {
+        JOURNAL.with_borrow(|j| {
+            j.range((*identity, 0, 0)..=(*identity, TransactionId::MAX, InstructionIndex::MAX))
+                .filter(|(_, e)| range.contains(&e.timestamp))
+                .map(|((_, transaction_id, instruction), e)| (transaction_id, instruction, e.0))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remote_exchanges::response::OrderData;
+
+    fn order(id: &str) -> Response {
+        Response::Order(OrderData {
+            id: id.to_string(),
+            message: "".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_entries_by_principal_and_time() {
+        let identity = Principal::from_text("aaaaa-aa").unwrap();
+        let other = Principal::anonymous();
+
+        Journal::append(&identity, 1, 0, Exchange::Okx, 100, &order("a"));
+        Journal::append(&identity, 1, 1, Exchange::Kraken, 200, &order("b"));
+        Journal::append(&identity, 2, 0, Exchange::Okx, 300, &order("c"));
+        Journal::append(&other, 3, 0, Exchange::Okx, 200, &order("d"));
+
+        // appending the same instruction again keeps the first entry
+        Journal::append(&identity, 1, 1, Exchange::Kraken, 250, &order("e"));
+
+        let entries = Journal::get_entries(&identity, 150..301);
+
+        assert_eq!(
+            entries
+                .iter()
+                .map(|(t, i, e)| (*t, *i, e.order_id.clone().unwrap(), e.timestamp))
+                .collect::<Vec<_>>(),
+            vec![(1, 1, "b".to_string(), 200), (2, 0, "c".to_string(), 300)]
+        );
+    }
+}
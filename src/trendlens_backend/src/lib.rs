@@ -1,33 +1,64 @@
 use std::str::FromStr;
 
 use crate::pair::Pair;
+use access::{is_admin, is_operator, is_trader, Access, Role};
+use api_client::transport::{self, RecordedCall, TransportKind};
 use api_store::{ApiData, ApiStore};
+use candid::Principal;
 use chain_data::{ExchangeData, TimestampBased};
+use config::{Config, InitArgs};
 use exchange::{Candle, Exchange, ExchangeImpl, TimeVolume};
-use ic_cdk::{query, update};
+use ic_cdk::{
+    api::management_canister::http_request::{HttpResponse, TransformArgs},
+    query, update,
+};
 use instruments::save_instruments;
+use interval::Interval;
+use journal::{Journal, JournalEntry};
+use quota::{Budget, Quota, QuotaLimits};
 use remote_exchanges::{
+    binance::{Binance, BinanceAuth},
     coinbase::{Coinbase, CoinbaseAuth},
+    kraken::{Kraken, KrakenAuth},
     okx::{api::InstrumentType, auth::OkxAuth, Okx},
     request::{GeneralPostOrderRequest, OrderSide, OrderType, TradeMode},
     ExchangeErrors, UserData,
 };
 use request_store::{
     request::{Request, Response},
-    Instruction, SignableInstruction, Transaction, TransactionStore,
+    Instruction, InstructionStatus, SignableInstruction, Transaction, TransactionStore,
 };
+use scheduler::{Job, Scheduler, MIN_PERIOD};
+use signer::{SealedSecret, SigningConsent};
 use storable_wrapper::StorableWrapper;
 use volume_store::{VolumesStore, VOLUME_STORE};
 
+/// ic_cdk::println traps outside of a canister, native builds (tests) print to stdout
+macro_rules! log {
+    ($($arg:tt)*) => {{
+        #[cfg(target_arch = "wasm32")]
+        ic_cdk::println!($($arg)*);
+        #[cfg(not(target_arch = "wasm32"))]
+        std::println!($($arg)*);
+    }};
+}
+
+mod access;
 mod api_client;
 mod api_store;
 mod chain_data;
+mod config;
 mod exchange;
 mod instruments;
+mod interval;
+mod journal;
 mod memory;
 mod pair;
+mod quota;
 mod remote_exchanges;
 mod request_store;
+mod scheduler;
+mod signer;
 mod storable_wrapper;
 mod volume_store;
 
@@ -36,6 +67,46 @@ fn __get_candid_interface_tmp_hack() -> String {
     include_str!("../trendlens_backend.did").to_string()
 }
 
+/// frontend derives both timestamps from the same instant (`signingTimestamps`),
+/// the margin only covers rounding by other clients
+const MAX_TIMESTAMP_DRIFT: u64 = 2;
+
+/// milliseconds of the signing instant, `timestamp_utc` is checked by [`validate_timestamps`]
+fn signing_millis(timestamp_utc: &str) -> Result<u64, ExchangeErrors> {
+    let millis = chrono::DateTime::parse_from_rfc3339(timestamp_utc)
+        .map_err(|_| ExchangeErrors::InvalidTimestamps)?
+        .timestamp_millis();
+
+    u64::try_from(millis).map_err(|_| ExchangeErrors::InvalidTimestamps)
+}
+
+/// rejects signature sets whose timestamps disagree or are too far from canister time,
+/// `now` is in seconds
+fn validate_timestamps(
+    timestamp_utc: &str,
+    timestamp: u64,
+    now: u64,
+    max_skew: u64,
+) -> Result<(), ExchangeErrors> {
+    let utc = chrono::DateTime::parse_from_rfc3339(timestamp_utc)
+        .map_err(|_| ExchangeErrors::InvalidTimestamps)?
+        .timestamp();
+
+    if utc < 0 || (utc as u64).abs_diff(timestamp) > MAX_TIMESTAMP_DRIFT {
+        return Err(ExchangeErrors::InvalidTimestamps);
+    }
+
+    if timestamp.abs_diff(now) > max_skew {
+        return Err(ExchangeErrors::ExpiredSignature {
+            timestamp,
+            now,
+            max_skew,
+        });
+    }
+
+    Ok(())
+}
+
 /// considering that stop > start
 fn get_range_to_fetch(stop: u64, current: u64) -> Option<std::ops::Range<u64>> {
     (stop > current).then_some(current..stop)
@@ -43,15 +114,18 @@ fn get_range_to_fetch(stop: u64, current: u64) -> Option<std::ops::Range<u64>> {
 
 // TODO: rename or get rid off
 #[ic_cdk::query]
-fn get_last_timestamp(exchange: Exchange, pair: String) -> Option<u64> {
+fn get_last_timestamp(exchange: Exchange, pair: String, interval: Interval) -> Option<u64> {
     let exchange_impl = ExchangeImpl::new(exchange);
 
     let pair = Pair::from_str(&pair).expect("invalid pair");
 
-    exchange_impl.get_data(pair)?.candles.last_timestamp()
+    exchange_impl
+        .get_data(pair, interval)?
+        .candles
+        .last_timestamp()
 }
 
-#[ic_cdk::update]
+#[update(guard = "is_trader")]
 fn register_api_key(exchange_api: ApiData) -> bool {
     let principal = ic_cdk::caller();
 
@@ -60,38 +134,81 @@ fn register_api_key(exchange_api: ApiData) -> bool {
     true
 }
 
-#[ic_cdk::update]
+#[update]
 fn remove_api_key(api_key: String) -> Option<ApiData> {
     let principal = ic_cdk::caller();
 
     ApiStore::remove_key(&principal, &api_key)
 }
 
-#[ic_cdk::update]
+/// opts the api key in to canister signing
+///
+/// the secret is sealed with chacha20-poly1305 under a master key which is stored
+/// in canister state next to the sealed secrets, so anyone who can read canister
+/// state (node providers, replicas) can decrypt it; treat a stored secret as
+/// unencrypted and limit the api key permissions to what the consent allows
+#[update(guard = "is_trader")]
+async fn store_api_secret(
+    api_key: String,
+    secret: String,
+    consent: SigningConsent,
+) -> Result<(), ExchangeErrors> {
+    let principal = ic_cdk::caller();
+    let master_key = signer::ensure_master_key().await?;
+    let nonce = signer::random_bytes().await?;
+
+    ApiStore::store_secret(
+        &principal,
+        &api_key,
+        SealedSecret::seal(&master_key, &nonce, &secret)?,
+        consent,
+    )
+    .ok_or_else(|| ExchangeErrors::SigningNotPermitted {
+        message: format!("api key {} is not registered", api_key),
+    })
+}
+
+#[update(guard = "is_trader")]
+fn set_signing_consent(api_key: String, consent: SigningConsent) -> bool {
+    ApiStore::set_consent(&ic_cdk::caller(), &api_key, consent).is_some()
+}
+
+#[update]
+fn remove_api_secret(api_key: String) -> bool {
+    ApiStore::remove_secret(&ic_cdk::caller(), &api_key)
+}
+
+#[query]
+fn get_signing_consent(api_key: String) -> Option<SigningConsent> {
+    ApiStore::get_signing_key(&ic_cdk::caller(), &api_key).map(|k| k.consent)
+}
+
+#[update(guard = "is_trader")]
 fn add_transaction(instruction: Vec<Instruction>) -> (u32, Transaction) {
-    let identity = ic_cdk::caller();
+    store_transaction(&ic_cdk::caller(), instruction)
+}
 
+fn store_transaction(identity: &Principal, instruction: Vec<Instruction>) -> (u32, Transaction) {
     let instructions = instruction
         .into_iter()
         .map(|i| {
             let exchange_impl = ExchangeImpl::new(i.exchange);
-            let signature = exchange_impl.get_signature_string(&i.request);
+            // requests sent without signature carry an empty payload
+            let signature = exchange_impl
+                .get_signature_string(&i.request)
+                .unwrap_or_default();
 
-            SignableInstruction {
-                instruction: i,
-                signature,
-                executed: false,
-            }
+            SignableInstruction::new(i, signature)
         })
         .collect::<Vec<_>>();
 
-    let index = TransactionStore::add_transaction(&identity, instructions);
-    let tx = TransactionStore::get_transaction(&identity, index).expect("missing tx");
+    let index = TransactionStore::add_transaction(identity, instructions);
+    let tx = TransactionStore::get_transaction(identity, index).expect("missing tx");
 
     (index, tx)
 }
 
-#[ic_cdk::update]
+#[update]
 fn delete_transaction(index: u32) {
     let identity = ic_cdk::caller();
 
@@ -112,7 +229,7 @@ fn get_transactions() -> Option<Vec<(u32, Transaction)>> {
     TransactionStore::get_transactions(&identity)
 }
 
-#[ic_cdk::update]
+#[update(guard = "is_operator")]
 async fn refresh_instruments(
     exchange: Exchange,
     instrument_type: InstrumentType,
@@ -130,7 +247,7 @@ fn get_instruments(exchange: Exchange, instrument_type: InstrumentType) -> Vec<P
     exchange_impl.get_pairs(instrument_type)
 }
 
-#[ic_cdk::update]
+#[update(guard = "is_trader")]
 async fn get_orderbook(exchange: Exchange, pair: String) -> f64 {
     let exchange_impl = ExchangeImpl::new(exchange);
     let pair = Pair::from_str(&pair).expect("invalid pair");
@@ -141,32 +258,147 @@ async fn get_orderbook(exchange: Exchange, pair: String) -> f64 {
     volume
 }
 
-#[ic_cdk::update]
+#[update(guard = "is_trader")]
 async fn run_transaction(
     index: u32,
     signature: Vec<String>,
     timestamp_utc: String,
     timestamp: u64,
 ) -> Result<Vec<Response>, ExchangeErrors> {
-    let identity = ic_cdk::caller();
-    let tx = TransactionStore::get_transaction(&identity, index).expect("missing transaction");
+    execute_transaction(
+        &ic_cdk::caller(),
+        index,
+        signature,
+        timestamp_utc,
+        timestamp,
+        ic_cdk::api::time,
+    )
+    .await
+}
 
-    ic_cdk::println!("{:?}", tx);
+/// runs the transaction with signatures made by the canister from stored secrets
+#[update(guard = "is_trader")]
+async fn run_signed_transaction(index: u32) -> Result<Vec<Response>, ExchangeErrors> {
+    let master_key = signer::master_key().ok_or_else(|| ExchangeErrors::SigningNotPermitted {
+        message: "no api secret is stored".to_string(),
+    })?;
+
+    execute_signed_transaction(&ic_cdk::caller(), index, &master_key, ic_cdk::api::time).await
+}
+
+async fn execute_signed_transaction(
+    identity: &Principal,
+    index: u32,
+    master_key: &[u8],
+    now: fn() -> u64,
+) -> Result<Vec<Response>, ExchangeErrors> {
+    let nanos = now();
+    let timestamp = nanos / 1_000_000_000;
+    let timestamp_utc = chrono::DateTime::from_timestamp_millis((nanos / 1_000_000) as i64)
+        .expect("canister time out of range")
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+
+    let signatures = sign_transaction(identity, index, master_key, &timestamp_utc, timestamp)?;
+
+    execute_transaction(identity, index, signatures, timestamp_utc, timestamp, now).await
+}
+
+/// executed instructions are skipped, so they are left unsigned
+fn sign_transaction(
+    identity: &Principal,
+    index: u32,
+    master_key: &[u8],
+    timestamp_utc: &str,
+    timestamp: u64,
+) -> Result<Vec<String>, ExchangeErrors> {
+    let tx =
+        TransactionStore::get_transaction(identity, index).ok_or(ExchangeErrors::InvalidIndex)?;
+    let millis = signing_millis(timestamp_utc)?;
+
+    tx.iter()
+        .enumerate()
+        .map(|(position, i)| {
+            if i.executed() {
+                return Ok(String::new());
+            }
+
+            let api_key = &i.instruction.api_key;
+            let key = ApiStore::get_signing_key(identity, api_key).ok_or_else(|| {
+                ExchangeErrors::SigningNotPermitted {
+                    message: format!("api key {} is not opted in", api_key),
+                }
+            })?;
+
+            if !key.consent.allows(&i.instruction.request) {
+                return Err(ExchangeErrors::SigningNotPermitted {
+                    message: format!("api key {} does not consent to the request", api_key),
+                });
+            }
+
+            // every execution signs a fresh kraken nonce, so retried legs are accepted
+            let signature_data = match i.instruction.exchange {
+                Exchange::Kraken => {
+                    Kraken::with_nonce(&i.signature, Kraken::execution_nonce(millis, position))
+                }
+                _ => i.signature.clone(),
+            };
+
+            signer::sign(
+                i.instruction.exchange,
+                &key.secret.open(master_key)?,
+                &signature_data,
+                timestamp_utc,
+                timestamp,
+            )
+        })
+        .collect()
+}
+
+/// `now` gives canister time in nanoseconds, signature timestamps are checked against it
+/// and it is recorded in the journal once a leg succeeds
+async fn execute_transaction(
+    identity: &Principal,
+    index: u32,
+    signature: Vec<String>,
+    timestamp_utc: String,
+    timestamp: u64,
+    now: fn() -> u64,
+) -> Result<Vec<Response>, ExchangeErrors> {
+    validate_timestamps(
+        &timestamp_utc,
+        timestamp,
+        now() / 1_000_000_000,
+        Config::max_timestamp_skew(),
+    )?;
+    let millis = signing_millis(&timestamp_utc)?;
+
+    let tx =
+        TransactionStore::get_transaction(identity, index).ok_or(ExchangeErrors::InvalidIndex)?;
+
+    // executed instructions take an empty signature, so every instruction has one
+    if signature.len() != tx.len() {
+        return Err(ExchangeErrors::SignatureCountMismatch {
+            expected: tx.len() as u32,
+            received: signature.len() as u32,
+        });
+    }
+
+    log!("{:?}", tx);
 
     let mut responses = vec![];
-    let mut instructions = vec![];
+    let mut instructions = tx.0.clone();
     let mut done_count = 0usize;
 
-    ic_cdk::println!("instructions len {}", tx.iter().count());
+    log!("instructions len {}", tx.iter().count());
 
-    for (i, signature) in tx.iter().zip(signature.iter()) {
-        if i.executed {
+    for (position, (i, signature)) in tx.iter().zip(signature.iter()).enumerate() {
+        if i.executed() {
             continue;
         }
 
-        ic_cdk::println!("executing: {}", signature);
+        log!("executing: {}", signature);
         let api_info =
-            ApiStore::get_by_api(&identity, &i.instruction.api_key).expect("api info not found");
+            ApiStore::get_by_api(identity, &i.instruction.api_key).expect("api info not found");
 
         let exchange: Box<dyn UserData> = match i.instruction.exchange {
             Exchange::Okx => Box::new(Okx::with_auth(OkxAuth {
@@ -181,6 +413,19 @@ async fn run_transaction(
                 signature: signature.clone(),
                 timestamp,
             })),
+            Exchange::Binance => Box::new(Binance::with_auth(BinanceAuth {
+                api_key: i.instruction.api_key.clone(),
+                signature: signature.clone(),
+                timestamp: timestamp * 1000,
+            })),
+            // the request has to carry the nonce that was signed
+            Exchange::Kraken => Box::new(Kraken::with_auth(KrakenAuth {
+                api_key: i.instruction.api_key.clone(),
+                signature: signature.clone(),
+                nonce: Kraken::execution_nonce(millis, position),
+            })),
+            #[cfg(test)]
+            Exchange::Mock => Box::new(remote_exchanges::mock::Mock),
         };
 
         let response = match i.instruction.request.clone() {
@@ -190,58 +435,114 @@ async fn run_transaction(
             Request::Instruments(instruments) => exchange.get_instruments(instruments).await,
             Request::Balances(balance) => exchange.get_balance(balance).await,
             Request::PostOrder(order) => exchange.post_order(order).await,
+            Request::CancelOrder(order) => exchange.cancel_order(order).await,
+            Request::AmendOrder(order) => exchange.amend_order(order).await,
+            Request::OrderDetails(order) => exchange.get_order_details(order).await,
+            Request::Fills(fills) => exchange.get_fills(fills).await,
             Request::OrdersList(orders_request) => match orders_request.pending {
                 true => exchange.get_pending_orders(orders_request).await,
                 false => exchange.get_done_orders(orders_request).await,
             },
         };
 
-        let instruction = if let Ok(r) = response {
-            ic_cdk::println!("execution successful: {:?}", r);
-            responses.push(r);
-            done_count += 1;
-            SignableInstruction {
-                instruction: i.instruction.clone(),
-                signature: i.signature.clone(),
-                executed: true,
+        let instruction = &mut instructions[position];
+        instruction.attempts += 1;
+
+        match response {
+            Ok(r) => {
+                log!("execution successful: {:?}", r);
+                Journal::append(
+                    identity,
+                    index,
+                    position as u32,
+                    i.instruction.exchange,
+                    now(),
+                    &r,
+                );
+                responses.push(r);
+                done_count += 1;
+                instruction.status = InstructionStatus::Succeeded;
+                instruction.error = None;
             }
-        } else {
-            ic_cdk::println!("execution failed");
-            i.clone()
-        };
+            Err(e) => {
+                log!("execution failed: {}", e);
+                instruction.status = InstructionStatus::Failed;
+                instruction.error = Some(e.to_string());
+            }
+        }
 
-        instructions.push(instruction);
+        // stored after every leg, so finished legs are not lost if a later one traps
+        TransactionStore::update_transaction(identity, index, instructions.clone());
     }
 
-    ic_cdk::println!("executed {}", done_count);
-
-    TransactionStore::delete_transaction(&identity, index);
-
-    if tx.iter().count() != done_count {
-        TransactionStore::add_transaction(&identity, instructions);
-    }
+    log!("executed {}", done_count);
 
     Ok(responses)
 }
 
 #[ic_cdk::init]
-fn init() {
+fn init(args: Option<InitArgs>) {
+    memory::migrations::record_current_versions();
+    apply_init_args(args.unwrap_or_default());
+
     let btc_usd_pair: Pair = Pair::from_str("btc-usd").expect("invalid pair");
+    let btc_eur_pair: Pair = Pair::from_str("btc-eur").expect("invalid pair");
     let exchange = ExchangeImpl::new(Exchange::Okx);
-    exchange.set_data(btc_usd_pair, StorableWrapper(ExchangeData::default()));
 
-    let btc_eur_pair: Pair = Pair::from_str("btc-eur").expect("invalid pair");
-    exchange.set_data(btc_eur_pair, StorableWrapper(ExchangeData::default()));
+    for interval in Interval::ALL {
+        exchange.set_data(
+            btc_usd_pair.clone(),
+            interval,
+            StorableWrapper(ExchangeData::default()),
+        );
+        exchange.set_data(
+            btc_eur_pair.clone(),
+            interval,
+            StorableWrapper(ExchangeData::default()),
+        );
+    }
+}
+
+#[ic_cdk::post_upgrade]
+fn post_upgrade(args: Option<InitArgs>) {
+    memory::migrations::run_migrations();
+    apply_init_args(args.unwrap_or_default());
+    Scheduler::arm_all();
+}
+
+fn apply_init_args(args: InitArgs) {
+    if let Some(proxy_canister_id) = args.proxy_canister_id {
+        Config::set_proxy_canister_id(proxy_canister_id);
+    }
+
+    if let Some(max_timestamp_skew) = args.max_timestamp_skew {
+        Config::set_max_timestamp_skew(max_timestamp_skew);
+    }
+
+    if let Some(proxy_method_override) = args.proxy_method_override {
+        Config::set_proxy_method_override(proxy_method_override);
+    }
+
+    for admin in args.admins.unwrap_or_default() {
+        Access::grant(admin, Role::Admin);
+    }
 }
 
-#[ic_cdk::update]
+#[update(guard = "is_operator")]
 fn initialize_pair(pair: String, exchange: Exchange) {
     let pair: Pair = Pair::from_str(&pair).expect("invalid pair");
     let exchange = ExchangeImpl::new(exchange);
-    exchange.set_data(pair.clone(), StorableWrapper(ExchangeData::default()));
+
+    for interval in Interval::ALL {
+        exchange.set_data(
+            pair.clone(),
+            interval,
+            StorableWrapper(ExchangeData::default()),
+        );
+    }
 }
 
-#[ic_cdk::update]
+#[update(guard = "is_trader")]
 async fn split_transaction(
     keys: Vec<ApiData>,
     pair: String,
@@ -251,14 +552,37 @@ async fn split_transaction(
     volume_ratios: Vec<f64>,
     ratios_weights: u32,
 ) -> Result<(u32, Transaction), ExchangeErrors> {
-    let identity = ic_cdk::caller();
+    split_order(
+        &ic_cdk::caller(),
+        keys,
+        pair,
+        order_side,
+        size,
+        price_limit,
+        volume_ratios,
+        ratios_weights,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn split_order(
+    identity: &Principal,
+    keys: Vec<ApiData>,
+    pair: String,
+    order_side: OrderSide,
+    size: f64,
+    price_limit: u32,
+    volume_ratios: Vec<f64>,
+    ratios_weights: u32,
+) -> Result<(u32, Transaction), ExchangeErrors> {
     let pair = Pair::from_str(&pair).expect("invalid pair");
 
     let mut volumes: Vec<f64> = vec![];
 
     for k in keys.iter() {
         let key =
-            ApiStore::get_by_api(&identity, &k.api_key).ok_or(ExchangeErrors::MissingApiKey)?;
+            ApiStore::get_by_api(identity, &k.api_key).ok_or(ExchangeErrors::MissingApiKey)?;
         let exchange = ExchangeImpl::new(key.exchange);
 
         volumes.push(
@@ -279,7 +603,7 @@ async fn split_transaction(
 
     let trade_cuts = weights.iter().map(|w| w * size).collect::<Vec<_>>();
 
-    ic_cdk::println!("Trade cuts: {:?}", trade_cuts);
+    log!("Trade cuts: {:?}", trade_cuts);
 
     let instructions: Vec<Result<Instruction, ExchangeErrors>> = keys
         .iter()
@@ -306,15 +630,16 @@ async fn split_transaction(
 
     let instructions = instructions.into_iter().collect::<Result<Vec<_>, _>>()?;
 
-    Ok(add_transaction(instructions))
+    Ok(store_transaction(identity, instructions))
 }
 
 // TODO: split this function into smaller ones
 // TODO: handle errors, return to caller
-#[ic_cdk::update]
+#[update(guard = "is_trader")]
 async fn pull_candles(
     pair: String,
     exchange: Exchange,
+    interval: Interval,
     start_timestamp: u64,
     end_timestamp: u64,
 ) -> Result<Vec<Candle>, ExchangeErrors> {
@@ -325,27 +650,30 @@ async fn pull_candles(
     let pair = Pair::from_str(&pair).expect("invalid pair");
     let exchange = ExchangeImpl::new(exchange);
     let mut exchange_data = exchange
-        .get_data(pair.clone())
+        .get_data(pair.clone(), interval)
         .ok_or(ExchangeErrors::MissingCandles)?;
 
     let last_candle_timestamp = exchange_data
         .candles
         .last_timestamp()
         .unwrap_or(start_timestamp);
-    ic_cdk::println!("Last candle timestamp: {}", last_candle_timestamp);
+    log!("Last candle timestamp: {}", last_candle_timestamp);
 
     let range_to_fetch = get_range_to_fetch(end_timestamp, last_candle_timestamp);
-    ic_cdk::println!("Range to fetch: {:?}", range_to_fetch);
+    log!("Range to fetch: {:?}", range_to_fetch);
 
-    // !!!! hardcoded interval
     let fetched_candles = match range_to_fetch {
-        Some(ref range) => exchange.fetch_candles(&pair, range.clone(), 60).await?,
+        Some(ref range) => {
+            exchange
+                .fetch_candles(&pair, range.clone(), interval)
+                .await?
+        }
         None => {
             vec![]
         }
     };
 
-    ic_cdk::println!("Fetched candles: {:?}", fetched_candles.len());
+    log!("Fetched candles: {:?}", fetched_candles.len());
 
     let range_to_get = match range_to_fetch {
         Some(_) if start_timestamp <= last_candle_timestamp => {
@@ -355,14 +683,14 @@ async fn pull_candles(
         None => Some(start_timestamp..end_timestamp),
     };
 
-    ic_cdk::println!("Range to get: {:?}", range_to_get);
+    log!("Range to get: {:?}", range_to_get);
 
     let stored_candles = range_to_get
         .and_then(|range| Some(exchange_data.candles.get_between(range)))
         .unwrap_or_default();
 
     exchange_data.candles.insert_many(fetched_candles.clone());
-    exchange.set_data(pair, exchange_data);
+    exchange.set_data(pair, interval, exchange_data);
 
     Ok(fetched_candles
         .into_iter()
@@ -370,6 +698,98 @@ async fn pull_candles(
         .collect::<Vec<_>>())
 }
 
+fn find_candle_gaps(
+    exchange: &ExchangeImpl,
+    pair: &Pair,
+    interval: Interval,
+    range: std::ops::Range<u64>,
+) -> Result<Vec<(u64, u64)>, ExchangeErrors> {
+    if range.is_empty() {
+        return Err(ExchangeErrors::InvalidTimestamps);
+    }
+
+    let exchange_data = exchange
+        .get_data(pair.clone(), interval)
+        .ok_or(ExchangeErrors::MissingCandles)?;
+
+    Ok(exchange_data
+        .candles
+        .find_gaps(interval, range)
+        .into_iter()
+        .map(|gap| (gap.start, gap.end))
+        .collect())
+}
+
+#[ic_cdk::query]
+fn get_candle_gaps(
+    exchange: Exchange,
+    pair: String,
+    interval: Interval,
+    start_timestamp: u64,
+    end_timestamp: u64,
+) -> Result<Vec<(u64, u64)>, ExchangeErrors> {
+    let pair = Pair::from_str(&pair).expect("invalid pair");
+    let exchange = ExchangeImpl::new(exchange);
+
+    find_candle_gaps(&exchange, &pair, interval, start_timestamp..end_timestamp)
+}
+
+/// fetches candles only for ranges missing in the store, returns gaps which
+/// are still missing afterwards (e.g. minutes without any trades)
+#[update(guard = "is_operator")]
+async fn backfill_candles(
+    pair: String,
+    exchange: Exchange,
+    interval: Interval,
+    start_timestamp: u64,
+    end_timestamp: u64,
+) -> Result<Vec<(u64, u64)>, ExchangeErrors> {
+    let pair = Pair::from_str(&pair).expect("invalid pair");
+    let exchange = ExchangeImpl::new(exchange);
+    let range = start_timestamp..end_timestamp;
+
+    for (start, end) in find_candle_gaps(&exchange, &pair, interval, range.clone())? {
+        let fetched_candles = exchange.fetch_candles(&pair, start..end, interval).await?;
+
+        // store may have changed while awaiting, read it again before writing
+        let mut exchange_data = exchange
+            .get_data(pair.clone(), interval)
+            .ok_or(ExchangeErrors::MissingCandles)?;
+        exchange_data.candles.insert_many(fetched_candles);
+        exchange.set_data(pair.clone(), interval, exchange_data);
+    }
+
+    find_candle_gaps(&exchange, &pair, interval, range)
+}
+
+/// returns completed buckets only, the one still forming is left out
+#[ic_cdk::query]
+fn get_aggregated_candles(
+    exchange: Exchange,
+    pair: String,
+    interval: Interval,
+    start_timestamp: u64,
+    end_timestamp: u64,
+) -> Result<Vec<Candle>, ExchangeErrors> {
+    if start_timestamp >= end_timestamp {
+        return Err(ExchangeErrors::InvalidTimestamps);
+    }
+
+    let pair = Pair::from_str(&pair).expect("invalid pair");
+    let exchange = ExchangeImpl::new(exchange);
+
+    // higher timeframes are rolled up from the 1m base series
+    let exchange_data = exchange
+        .get_data(pair, Interval::OneMinute)
+        .ok_or(ExchangeErrors::MissingCandles)?;
+
+    Ok(exchange_data
+        .candles
+        .aggregate(interval, start_timestamp..end_timestamp))
+}
+
+/// okx volumes cover all spot pairs of the base currency, they are stored
+/// under the usdt pair only
 #[query]
 fn get_volumes(exchange: Exchange, pair: String, start: u64) -> Option<Vec<TimeVolume>> {
     let pair = Pair::from_str(&pair).expect("invalid pair");
@@ -381,7 +801,7 @@ fn get_volumes(exchange: Exchange, pair: String, start: u64) -> Option<Vec<TimeV
     })
 }
 
-#[update]
+#[update(guard = "is_operator")]
 fn initialize_volume_store(exchange: Exchange, pair: String, timestamp: u64) {
     let pair = Pair::from_str(&pair).expect("invalid pair");
 
@@ -392,19 +812,13 @@ fn initialize_volume_store(exchange: Exchange, pair: String, timestamp: u64) {
             v.get(&(exchange, pair.clone())).unwrap()
         });
 
-        store.insert(
-            timestamp,
-            TimeVolume {
-                timestamp,
-                volume: 0.0,
-            },
-        );
+        store.insert(timestamp, TimeVolume::new(timestamp, 0.0));
 
         v.insert((exchange, pair), store);
     });
 }
 
-#[update]
+#[update(guard = "is_operator")]
 async fn pull_volumes(
     exchange: Exchange,
     pair: String,
@@ -443,11 +857,145 @@ async fn pull_volumes(
     Ok(fetched_volumes)
 }
 
+#[update(guard = "is_admin")]
+fn add_ingestion_job(exchange: Exchange, pair: String, interval: Interval, period: u64) -> u32 {
+    if period < MIN_PERIOD {
+        ic_cdk::trap(&format!("period has to be at least {} seconds", MIN_PERIOD));
+    }
+
+    let pair = Pair::from_str(&pair).expect("invalid pair");
+    let exchange_impl = ExchangeImpl::new(exchange);
+
+    if exchange_impl.get_data(pair.clone(), interval).is_none() {
+        exchange_impl.set_data(
+            pair.clone(),
+            interval,
+            StorableWrapper(ExchangeData::default()),
+        );
+    }
+
+    Scheduler::add_job(exchange, pair, interval, period)
+}
+
+#[update(guard = "is_admin")]
+fn remove_ingestion_job(id: u32) -> bool {
+    Scheduler::remove_job(id)
+}
+
+#[update(guard = "is_admin")]
+fn pause_ingestion_job(id: u32) -> bool {
+    Scheduler::set_paused(id, true)
+}
+
+#[update(guard = "is_admin")]
+fn resume_ingestion_job(id: u32) -> bool {
+    Scheduler::set_paused(id, false)
+}
+
+#[query(guard = "is_admin")]
+fn get_ingestion_jobs() -> Vec<(u32, Job)> {
+    Scheduler::get_jobs()
+}
+
+#[query]
+fn get_journal(start: u64, end: u64) -> Vec<(u32, u32, JournalEntry)> {
+    Journal::get_entries(&ic_cdk::caller(), start..end)
+}
+
+#[query(guard = "is_admin")]
+fn get_principal_journal(
+    principal: Principal,
+    start: u64,
+    end: u64,
+) -> Vec<(u32, u32, JournalEntry)> {
+    Journal::get_entries(&principal, start..end)
+}
+
+#[update(guard = "is_admin")]
+fn set_proxy_canister(proxy_canister_id: Principal) {
+    Config::set_proxy_canister_id(proxy_canister_id)
+}
+
+#[update(guard = "is_admin")]
+fn set_max_timestamp_skew(max_timestamp_skew: u64) {
+    Config::set_max_timestamp_skew(max_timestamp_skew)
+}
+
+/// enable only for a proxy that sends requests with the method of their
+/// `X-HTTP-Method-Override` header, otherwise coinbase and binance cancels are refused
+#[update(guard = "is_admin")]
+fn set_proxy_method_override(proxy_method_override: bool) {
+    Config::set_proxy_method_override(proxy_method_override)
+}
+
+#[query]
+fn get_config() -> Config {
+    Config::get()
+}
+
+#[update(guard = "is_admin")]
+fn grant_role(principal: Principal, role: Role) {
+    Access::grant(principal, role)
+}
+
+#[update(guard = "is_admin")]
+fn revoke_role(principal: Principal) -> Option<Role> {
+    Access::revoke(&principal)
+}
+
+#[query(guard = "is_admin")]
+fn get_roles() -> Vec<(Principal, Role)> {
+    Access::get_roles()
+}
+
+#[update(guard = "is_admin")]
+fn set_quota_limits(limits: QuotaLimits) {
+    Quota::set_limits(limits)
+}
+
+#[query]
+fn get_quota_limits() -> QuotaLimits {
+    Quota::limits()
+}
+
+#[query]
+fn get_remaining_budget() -> Budget {
+    Quota::remaining(&ic_cdk::caller(), ic_cdk::api::time() / 1_000_000_000)
+}
+
+#[update(guard = "is_admin")]
+fn set_transport(exchange: Exchange, transport: TransportKind) {
+    TransportKind::set(exchange, transport)
+}
+
+#[query]
+fn get_transport(exchange: Exchange) -> TransportKind {
+    TransportKind::get(exchange)
+}
+
+#[query(guard = "is_admin")]
+fn get_recorded_traffic() -> Vec<RecordedCall> {
+    transport::recorded_traffic()
+}
+
+#[update(guard = "is_admin")]
+fn load_recorded_traffic(calls: Vec<RecordedCall>) {
+    transport::load_traffic(calls)
+}
+
+#[query]
+fn transform_http_response(args: TransformArgs) -> HttpResponse {
+    transport::transform_response(args.response)
+}
+
 ic_cdk::export_candid!();
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::executor::block_on;
+    use ic_cdk::api::management_canister::http_request::HttpMethod;
+    use remote_exchanges::request::GeneralOrderDetailsRequest;
 
     #[test]
     fn test_first_timestamp_to_fetch() {
@@ -464,4 +1012,439 @@ mod tests {
 
         assert_eq!(get_range_to_fetch(stop, current), None);
     }
+
+    #[test]
+    fn test_validate_timestamps() {
+        let now = 1_717_200_000;
+        let utc = "2024-06-01T00:00:00.715Z";
+
+        assert!(validate_timestamps(utc, now - 1, now, 30).is_ok());
+        assert!(matches!(
+            validate_timestamps("yesterday", now, now, 30),
+            Err(ExchangeErrors::InvalidTimestamps)
+        ));
+        assert!(matches!(
+            validate_timestamps(utc, now - 60, now, 30),
+            Err(ExchangeErrors::InvalidTimestamps)
+        ));
+        assert!(matches!(
+            validate_timestamps(utc, now, now + 31, 30),
+            Err(ExchangeErrors::ExpiredSignature { max_skew: 30, .. })
+        ));
+        // signatures from the future are refused as well
+        assert!(validate_timestamps(utc, now, now - 31, 30).is_err());
+    }
+
+    #[test]
+    fn test_frontend_signing_timestamps() {
+        let now = 1_717_200_000;
+
+        // signingTimestamps backdates 2024-06-01T00:00:00.499Z by a second
+        assert!(validate_timestamps("2024-05-31T23:59:59.499Z", now - 1, now, 30).is_ok());
+        // seconds rounded up from the same instant
+        assert!(validate_timestamps("2024-05-31T23:59:59.499Z", now, now, 30).is_ok());
+        // previous clients backdated only the seconds by four
+        assert!(matches!(
+            validate_timestamps("2024-06-01T00:00:00.499Z", now - 4, now, 30),
+            Err(ExchangeErrors::InvalidTimestamps)
+        ));
+    }
+
+    fn mock_key(api_key: &str) -> ApiData {
+        ApiData {
+            exchange: Exchange::Mock,
+            api_key: api_key.to_string(),
+            passphrase: None,
+        }
+    }
+
+    #[test]
+    fn test_pull_candles_mock() {
+        let pair = Pair::from_str("btc-usd").unwrap();
+        ExchangeImpl::new(Exchange::Mock).set_data(
+            pair,
+            Interval::OneMinute,
+            StorableWrapper(ExchangeData::default()),
+        );
+
+        let candles = block_on(pull_candles(
+            "btc-usd".to_string(),
+            Exchange::Mock,
+            Interval::OneMinute,
+            1717200000,
+            1717200180,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            candles.iter().map(|c| c.timestamp).collect::<Vec<_>>(),
+            vec![1717200000, 1717200060, 1717200120]
+        );
+
+        // stored candles are served without fetching again
+        let stored = block_on(pull_candles(
+            "btc-usd".to_string(),
+            Exchange::Mock,
+            Interval::OneMinute,
+            1717200000,
+            1717200120,
+        ))
+        .unwrap();
+
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0], candles[0]);
+    }
+
+    #[test]
+    fn test_split_and_run_transaction_mock() {
+        let identity = Principal::anonymous();
+        let keys = vec![mock_key("mock-key-1"), mock_key("mock-key-2")];
+
+        for key in keys.iter() {
+            ApiStore::register_key(&identity, key.clone()).unwrap();
+        }
+
+        let (index, tx) = block_on(split_order(
+            &identity,
+            keys,
+            "btc-usd".to_string(),
+            OrderSide::Buy,
+            1.0,
+            1,
+            vec![0.5, 0.5],
+            0,
+        ))
+        .unwrap();
+
+        let sizes = tx
+            .iter()
+            .map(|i| match i.instruction.request {
+                Request::PostOrder(ref order) => order.size,
+                _ => panic!("expected post order"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(sizes, vec![0.5, 0.5]);
+
+        let responses = block_on(execute_transaction(
+            &identity,
+            index,
+            vec!["".to_string(), "".to_string()],
+            "2024-06-01T00:00:00.000Z".to_string(),
+            1_717_200_000,
+            || 1_717_200_000_000_000_000,
+        ))
+        .unwrap();
+
+        let ids = responses
+            .into_iter()
+            .map(|r| match r {
+                Response::Order(order) => order.id,
+                _ => panic!("expected order response"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec!["mock-1", "mock-2"]);
+
+        // executed transaction keeps its id
+        let tx = TransactionStore::get_transaction(&identity, index).unwrap();
+        assert!(tx
+            .iter()
+            .all(|i| i.status == InstructionStatus::Succeeded && i.attempts == 1));
+
+        let journal = Journal::get_entries(&identity, 0..u64::MAX);
+        assert_eq!(
+            journal
+                .into_iter()
+                .map(|(t, i, e)| (t, i, e.order_id.unwrap()))
+                .collect::<Vec<_>>(),
+            vec![
+                (index, 0, "mock-1".to_string()),
+                (index, 1, "mock-2".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failed_instruction_status() {
+        let identity = Principal::anonymous();
+        let key = mock_key("mock-key-1");
+        ApiStore::register_key(&identity, key.clone()).unwrap();
+
+        let (index, _) = store_transaction(
+            &identity,
+            vec![Instruction {
+                exchange: Exchange::Mock,
+                api_key: key.api_key,
+                request: Request::OrderDetails(GeneralOrderDetailsRequest {
+                    instrument_id: Pair::from_str("btc-usd").unwrap(),
+                    order_id: "unknown".to_string(),
+                }),
+            }],
+        );
+
+        for attempt in 1..=2 {
+            let responses = block_on(execute_transaction(
+                &identity,
+                index,
+                vec!["".to_string()],
+                "2024-06-01T00:00:00.000Z".to_string(),
+                1_717_200_000,
+                || 1_717_200_000_000_000_000,
+            ))
+            .unwrap();
+
+            assert!(responses.is_empty());
+
+            let instruction = &TransactionStore::get_transaction(&identity, index).unwrap()[0];
+            assert_eq!(instruction.status, InstructionStatus::Failed);
+            assert_eq!(instruction.attempts, attempt);
+            assert_eq!(
+                instruction.error,
+                Some(ExchangeErrors::MissingOrder.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_transaction_or_missing_signatures_are_rejected() {
+        let identity = Principal::anonymous();
+        let key = mock_key("mock-key-4");
+        ApiStore::register_key(&identity, key.clone()).unwrap();
+
+        let order_details = || Instruction {
+            exchange: Exchange::Mock,
+            api_key: key.api_key.clone(),
+            request: Request::OrderDetails(GeneralOrderDetailsRequest {
+                instrument_id: Pair::from_str("btc-usd").unwrap(),
+                order_id: "unknown".to_string(),
+            }),
+        };
+        let (index, _) = store_transaction(&identity, vec![order_details(), order_details()]);
+        let run = |index: u32, signature: Vec<String>| {
+            block_on(execute_transaction(
+                &identity,
+                index,
+                signature,
+                "2024-06-01T00:00:00.000Z".to_string(),
+                1_717_200_000,
+                || 1_717_200_000_000_000_000,
+            ))
+        };
+
+        assert!(matches!(
+            run(index + 1, vec!["".to_string(), "".to_string()]),
+            Err(ExchangeErrors::InvalidIndex)
+        ));
+        assert!(matches!(
+            run(index, vec!["".to_string()]),
+            Err(ExchangeErrors::SignatureCountMismatch {
+                expected: 2,
+                received: 1
+            })
+        ));
+
+        let tx = TransactionStore::get_transaction(&identity, index).unwrap();
+        assert!(tx.iter().all(|i| i.attempts == 0));
+    }
+
+    #[test]
+    fn test_expired_signature_is_not_executed() {
+        let identity = Principal::anonymous();
+        let key = mock_key("mock-key-2");
+        ApiStore::register_key(&identity, key.clone()).unwrap();
+
+        let (index, _) = store_transaction(
+            &identity,
+            vec![Instruction {
+                exchange: Exchange::Mock,
+                api_key: key.api_key,
+                request: Request::OrderDetails(GeneralOrderDetailsRequest {
+                    instrument_id: Pair::from_str("btc-usd").unwrap(),
+                    order_id: "unknown".to_string(),
+                }),
+            }],
+        );
+
+        let result = block_on(execute_transaction(
+            &identity,
+            index,
+            vec!["".to_string()],
+            "2024-06-01T00:00:00.000Z".to_string(),
+            1_717_200_000,
+            || 1_717_200_600_000_000_000,
+        ));
+
+        assert!(matches!(
+            result,
+            Err(ExchangeErrors::ExpiredSignature { .. })
+        ));
+
+        let instruction = &TransactionStore::get_transaction(&identity, index).unwrap()[0];
+        assert_eq!(instruction.status, InstructionStatus::Pending);
+        assert_eq!(instruction.attempts, 0);
+    }
+
+    #[test]
+    fn test_signed_transaction_requires_consent() {
+        let identity = Principal::anonymous();
+        let key = mock_key("mock-key-3");
+        let master_key = [3u8; 32];
+        ApiStore::register_key(&identity, key.clone()).unwrap();
+
+        let (index, _) = store_transaction(
+            &identity,
+            vec![Instruction {
+                exchange: Exchange::Mock,
+                api_key: key.api_key.clone(),
+                request: Request::OrderDetails(GeneralOrderDetailsRequest {
+                    instrument_id: Pair::from_str("btc-usd").unwrap(),
+                    order_id: "unknown".to_string(),
+                }),
+            }],
+        );
+        let run = || {
+            block_on(execute_signed_transaction(
+                &identity,
+                index,
+                &master_key,
+                || 1_717_200_000_000_000_000,
+            ))
+        };
+
+        assert!(matches!(
+            run(),
+            Err(ExchangeErrors::SigningNotPermitted { .. })
+        ));
+
+        let secret = SealedSecret::seal(&master_key, &[4u8; 16], "secret").unwrap();
+        ApiStore::store_secret(&identity, &key.api_key, secret, SigningConsent::default()).unwrap();
+
+        assert!(matches!(
+            run(),
+            Err(ExchangeErrors::SigningNotPermitted { .. })
+        ));
+
+        let read = SigningConsent {
+            read: true,
+            trade: false,
+        };
+        ApiStore::set_consent(&identity, &key.api_key, read).unwrap();
+
+        assert!(run().unwrap().is_empty());
+
+        let instruction = &TransactionStore::get_transaction(&identity, index).unwrap()[0];
+        assert_eq!(instruction.status, InstructionStatus::Failed);
+    }
+
+    #[test]
+    fn test_retried_kraken_leg_signs_fresh_nonce() {
+        let identity = Principal::anonymous();
+        let master_key = [5u8; 32];
+        let key = ApiData {
+            exchange: Exchange::Kraken,
+            api_key: "kraken-key".to_string(),
+            passphrase: None,
+        };
+        ApiStore::register_key(&identity, key.clone()).unwrap();
+
+        let secret = SealedSecret::seal(&master_key, &[6u8; 12], "a3Jha2VuLXNlY3JldA==").unwrap();
+        let consent = SigningConsent {
+            read: true,
+            trade: true,
+        };
+        ApiStore::store_secret(&identity, &key.api_key, secret, consent).unwrap();
+
+        let (index, tx) = store_transaction(
+            &identity,
+            vec![Instruction {
+                exchange: Exchange::Kraken,
+                api_key: key.api_key.clone(),
+                request: Request::PostOrder(GeneralPostOrderRequest {
+                    instrument_id: Pair::from_str("btc-usd").unwrap(),
+                    trade_mode: TradeMode::Cash,
+                    side: OrderSide::Buy,
+                    margin_currency: None,
+                    position_side: None,
+                    order_type: OrderType::Limit,
+                    size: 0.5,
+                    order_price: Some(67000.5),
+                }),
+            }],
+        );
+
+        // stored signature data leaves the nonce to the execution
+        assert!(tx[0]
+            .signature
+            .contains(remote_exchanges::kraken::NONCE_PLACEHOLDER));
+
+        TransportKind::set(Exchange::Kraken, TransportKind::Replay);
+        let add_order = |body: &str| RecordedCall {
+            method: HttpMethod::POST,
+            url: "https://api.kraken.com/0/private/AddOrder".to_string(),
+            response: HttpResponse {
+                status: 200u32.into(),
+                headers: vec![],
+                body: body.as_bytes().to_vec(),
+            },
+        };
+        transport::load_traffic(vec![
+            add_order(r#"{"error":["EOrder:Insufficient funds"]}"#),
+            add_order(
+                r#"{"error":[],"result":{"descr":{"order":"buy 0.50000000 XBTUSD @ limit 67000.5"},"txid":["OUF4EM-FRGI2-MQMWZD"]}}"#,
+            ),
+        ]);
+
+        let run = |now: fn() -> u64| {
+            block_on(execute_signed_transaction(
+                &identity,
+                index,
+                &master_key,
+                now,
+            ))
+            .unwrap()
+        };
+
+        assert!(run(|| 1_717_200_000_000_000_000).is_empty());
+
+        let instruction = &TransactionStore::get_transaction(&identity, index).unwrap()[0];
+        assert_eq!(instruction.status, InstructionStatus::Failed);
+        assert_eq!(instruction.attempts, 1);
+
+        // the retry is signed over a nonce newer than the failed attempt
+        let signature = |timestamp_utc: &str, timestamp: u64| {
+            sign_transaction(&identity, index, &master_key, timestamp_utc, timestamp).unwrap()
+        };
+        let retried = signature("2024-06-01T00:00:05.000Z", 1_717_200_005);
+        assert_ne!(
+            retried,
+            signature("2024-06-01T00:00:00.000Z", 1_717_200_000)
+        );
+
+        let retried_data = Kraken::with_nonce(
+            &tx[0].signature,
+            Kraken::execution_nonce(1_717_200_005_000, 0),
+        );
+        assert_eq!(
+            retried[0],
+            signer::sign(
+                Exchange::Kraken,
+                "a3Jha2VuLXNlY3JldA==",
+                &retried_data,
+                "2024-06-01T00:00:05.000Z",
+                1_717_200_005,
+            )
+            .unwrap()
+        );
+
+        let responses = run(|| 1_717_200_005_000_000_000);
+        assert!(matches!(
+            &responses[..],
+            [Response::Order(order)] if order.id == "OUF4EM-FRGI2-MQMWZD"
+        ));
+
+        let instruction = &TransactionStore::get_transaction(&identity, index).unwrap()[0];
+        assert_eq!(instruction.status, InstructionStatus::Succeeded);
+        assert_eq!(instruction.attempts, 2);
+    }
 }
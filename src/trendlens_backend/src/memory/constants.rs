@@ -1,6 +1,7 @@
 use ic_stable_structures::memory_manager::MemoryId;
 
-pub const EXCHANGE_TABLE_MEMORY_ID: MemoryId = MemoryId::new(1);
+// candles keyed by (exchange, pair) only, emptied into the candles table by a migration
+pub const LEGACY_CANDLES_TABLE_MEMORY_ID: MemoryId = MemoryId::new(1);
 pub const USER_KEYS_TABLE_MEMORY_ID: MemoryId = MemoryId::new(2);
 pub const API_KEYS_TABLE_MEMORY_ID: MemoryId = MemoryId::new(3);
 pub const INSTRUCTIONS_TABLE_MEMORY_ID: MemoryId = MemoryId::new(4);
@@ -8,3 +9,15 @@ pub const TRANSACTIONS_TABLE_MEMORY_ID: MemoryId = MemoryId::new(5);
 pub const USER_TRANSACTIONS_TABLE_MEMORY_ID: MemoryId = MemoryId::new(6);
 pub const EXCHANGE_INSTRUMENTS_TABLE_MEMORY_ID: MemoryId = MemoryId::new(7);
 pub const VOLUMES_TABLE_MEMORY_ID: MemoryId = MemoryId::new(8);
+pub const CANDLES_TABLE_MEMORY_ID: MemoryId = MemoryId::new(9);
+pub const JOBS_TABLE_MEMORY_ID: MemoryId = MemoryId::new(10);
+pub const SCHEMA_TABLE_MEMORY_ID: MemoryId = MemoryId::new(11);
+pub const TRANSPORTS_TABLE_MEMORY_ID: MemoryId = MemoryId::new(12);
+pub const ROLES_TABLE_MEMORY_ID: MemoryId = MemoryId::new(13);
+pub const CONFIG_CELL_MEMORY_ID: MemoryId = MemoryId::new(14);
+pub const QUOTA_LIMITS_CELL_MEMORY_ID: MemoryId = MemoryId::new(15);
+pub const QUOTA_USAGE_TABLE_MEMORY_ID: MemoryId = MemoryId::new(16);
+pub const JOURNAL_TABLE_MEMORY_ID: MemoryId = MemoryId::new(17);
+pub const SIGNING_KEYS_TABLE_MEMORY_ID: MemoryId = MemoryId::new(18);
+pub const MASTER_KEY_CELL_MEMORY_ID: MemoryId = MemoryId::new(19);
+pub const TRANSACTION_COUNTER_CELL_MEMORY_ID: MemoryId = MemoryId::new(20);
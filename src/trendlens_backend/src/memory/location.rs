@@ -1,28 +1,55 @@
 use super::constants;
 use ic_stable_structures::memory_manager::MemoryId;
 
+// discriminants are persisted as keys of schema table, keep them stable
+#[repr(u8)]
+#[derive(Clone, Copy, Debug)]
 pub enum MemoryLocation {
-    Exchanges,
-    UserKeys,
-    ApiKeys,
-    Instructions,
-    Transactions,
-    UserTransactions,
-    ExchangeInstruments,
-    Volumes
+    LegacyCandles = 1,
+    UserKeys = 2,
+    ApiKeys = 3,
+    Instructions = 4,
+    Transactions = 5,
+    UserTransactions = 6,
+    ExchangeInstruments = 7,
+    Volumes = 8,
+    Candles = 9,
+    Jobs = 10,
+    Schema = 11,
+    Transports = 12,
+    Roles = 13,
+    Config = 14,
+    QuotaLimits = 15,
+    QuotaUsage = 16,
+    Journal = 17,
+    SigningKeys = 18,
+    MasterKey = 19,
+    TransactionCounter = 20,
 }
 
 impl MemoryLocation {
     pub fn memory_id(self) -> MemoryId {
         match self {
-            MemoryLocation::Exchanges => constants::EXCHANGE_TABLE_MEMORY_ID,
+            MemoryLocation::LegacyCandles => constants::LEGACY_CANDLES_TABLE_MEMORY_ID,
             MemoryLocation::UserKeys => constants::USER_KEYS_TABLE_MEMORY_ID,
             MemoryLocation::ApiKeys => constants::API_KEYS_TABLE_MEMORY_ID,
             MemoryLocation::Instructions => constants::INSTRUCTIONS_TABLE_MEMORY_ID,
             MemoryLocation::Transactions => constants::TRANSACTIONS_TABLE_MEMORY_ID,
             MemoryLocation::UserTransactions => constants::USER_TRANSACTIONS_TABLE_MEMORY_ID,
             MemoryLocation::ExchangeInstruments => constants::EXCHANGE_INSTRUMENTS_TABLE_MEMORY_ID,
-            MemoryLocation::Volumes => constants::VOLUMES_TABLE_MEMORY_ID
+            MemoryLocation::Volumes => constants::VOLUMES_TABLE_MEMORY_ID,
+            MemoryLocation::Candles => constants::CANDLES_TABLE_MEMORY_ID,
+            MemoryLocation::Jobs => constants::JOBS_TABLE_MEMORY_ID,
+            MemoryLocation::Schema => constants::SCHEMA_TABLE_MEMORY_ID,
+            MemoryLocation::Transports => constants::TRANSPORTS_TABLE_MEMORY_ID,
+            MemoryLocation::Roles => constants::ROLES_TABLE_MEMORY_ID,
+            MemoryLocation::Config => constants::CONFIG_CELL_MEMORY_ID,
+            MemoryLocation::QuotaLimits => constants::QUOTA_LIMITS_CELL_MEMORY_ID,
+            MemoryLocation::QuotaUsage => constants::QUOTA_USAGE_TABLE_MEMORY_ID,
+            MemoryLocation::Journal => constants::JOURNAL_TABLE_MEMORY_ID,
+            MemoryLocation::SigningKeys => constants::SIGNING_KEYS_TABLE_MEMORY_ID,
+            MemoryLocation::MasterKey => constants::MASTER_KEY_CELL_MEMORY_ID,
+            MemoryLocation::TransactionCounter => constants::TRANSACTION_COUNTER_CELL_MEMORY_ID,
         }
     }
 }
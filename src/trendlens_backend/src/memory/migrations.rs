@@ -0,0 +1,242 @@
+use super::{Memory, MemoryLocation, MEMORY_MANAGER};
+use crate::{
+    chain_data::ExchangeData,
+    exchange::{Exchange, ExchangeImpl},
+    interval::Interval,
+    pair::Pair,
+    remote_exchanges::okx::api::InstrumentType,
+    storable_wrapper::envelope,
+};
+use candid::Principal;
+use ic_stable_structures::{StableBTreeMap, Storable};
+use std::cell::RefCell;
+
+/// schema version of each memory location, keyed by location discriminant
+type SchemaTable = StableBTreeMap<u8, u16, Memory>;
+
+thread_local! {
+    static SCHEMA: RefCell<SchemaTable> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Schema.memory_id())),
+        )
+    );
+}
+
+pub struct Migration {
+    pub location: MemoryLocation,
+    /// schema version of the location after the migration ran
+    pub version: u16,
+    pub run: fn(MemoryLocation),
+}
+
+/// ordered list of all migrations, new entries are appended at the end
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        location: MemoryLocation::UserKeys,
+        version: 1,
+        run: wrap_legacy_values::<(Principal, u32)>,
+    },
+    Migration {
+        location: MemoryLocation::Instructions,
+        version: 1,
+        run: wrap_legacy_values::<u32>,
+    },
+    Migration {
+        location: MemoryLocation::Transactions,
+        version: 1,
+        run: wrap_legacy_values::<u32>,
+    },
+    Migration {
+        location: MemoryLocation::UserTransactions,
+        version: 1,
+        run: wrap_legacy_values::<Principal>,
+    },
+    Migration {
+        location: MemoryLocation::ExchangeInstruments,
+        version: 1,
+        run: wrap_legacy_values::<(Exchange, InstrumentType)>,
+    },
+    Migration {
+        location: MemoryLocation::Volumes,
+        version: 1,
+        run: wrap_legacy_values::<(Exchange, Pair)>,
+    },
+    Migration {
+        location: MemoryLocation::Candles,
+        version: 1,
+        run: wrap_legacy_values::<(Exchange, Pair, Interval)>,
+    },
+    Migration {
+        location: MemoryLocation::Jobs,
+        version: 1,
+        run: wrap_legacy_values::<u32>,
+    },
+    Migration {
+        location: MemoryLocation::LegacyCandles,
+        version: 1,
+        run: move_legacy_candles,
+    },
+];
+
+pub fn schema_version(location: MemoryLocation) -> u16 {
+    SCHEMA.with_borrow(|s| s.get(&(location as u8)).unwrap_or_default())
+}
+
+fn set_schema_version(location: MemoryLocation, version: u16) {
+    SCHEMA.with_borrow_mut(|s| s.insert(location as u8, version));
+}
+
+/// runs migrations newer than recorded schema versions, has to be called
+/// before any store of migrated location is touched
+pub fn run_migrations() {
+    for migration in MIGRATIONS {
+        if schema_version(migration.location) < migration.version {
+            log!(
+                "Migrating {:?} to version {}",
+                migration.location,
+                migration.version
+            );
+
+            (migration.run)(migration.location);
+            set_schema_version(migration.location, migration.version);
+        }
+    }
+}
+
+/// marks every location as up to date, used on fresh install
+pub fn record_current_versions() {
+    for migration in MIGRATIONS {
+        set_schema_version(migration.location, migration.version);
+    }
+}
+
+/// values written before the version header was introduced are plain bincode
+/// of the first schema version
+fn wrap_legacy_values<K: Storable + Ord + Clone>(location: MemoryLocation) {
+    let mut table: StableBTreeMap<K, Vec<u8>, Memory> =
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(location.memory_id())));
+
+    let entries = table.iter().collect::<Vec<_>>();
+
+    for (key, payload) in entries {
+        table.insert(key, envelope(1, &payload));
+    }
+}
+
+/// candles stored before intervals were introduced are one minute candles,
+/// they are merged into the one minute series without replacing newer ones
+fn move_legacy_candles(location: MemoryLocation) {
+    let mut table: StableBTreeMap<(Exchange, Pair), Vec<u8>, Memory> =
+        StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(location.memory_id())));
+
+    let entries = table.iter().collect::<Vec<_>>();
+
+    for ((exchange, pair), payload) in entries {
+        let legacy: ExchangeData =
+            bincode::deserialize(&payload).expect("failed to decode legacy candles");
+        let exchange_impl = ExchangeImpl::new(exchange);
+        let mut data = exchange_impl
+            .get_data(pair.clone(), Interval::OneMinute)
+            .unwrap_or_default();
+
+        for (timestamp, candle) in legacy.candles.iter() {
+            data.candles
+                .entry(*timestamp)
+                .or_insert_with(|| candle.clone());
+        }
+
+        exchange_impl.set_data(pair.clone(), Interval::OneMinute, data);
+        table.remove(&(exchange, pair));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chain_data::TimestampBased, exchange::Candle, scheduler::Job,
+        storable_wrapper::StorableWrapper,
+    };
+
+    #[test]
+    fn test_wrap_legacy_values() {
+        let job = Job {
+            exchange: Exchange::Okx,
+            pair: Pair {
+                base: "BTC".to_string(),
+                quote: "USD".to_string(),
+            },
+            interval: Interval::OneMinute,
+            period: 60,
+            paused: false,
+            last_run: None,
+        };
+
+        let memory = MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Jobs.memory_id()));
+        let mut legacy: StableBTreeMap<u32, Vec<u8>, Memory> = StableBTreeMap::init(memory);
+        legacy.insert(0, bincode::serialize(&job).unwrap());
+
+        run_migrations();
+
+        let memory = MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Jobs.memory_id()));
+        let jobs: StableBTreeMap<u32, StorableWrapper<Job>, Memory> = StableBTreeMap::init(memory);
+
+        assert_eq!(jobs.get(&0).unwrap().pair, job.pair);
+        assert_eq!(schema_version(MemoryLocation::Jobs), 1);
+
+        // already migrated locations are left untouched
+        run_migrations();
+        assert_eq!(jobs.get(&0).unwrap().period, 60);
+    }
+
+    #[test]
+    fn test_move_legacy_candles() {
+        let candle = |timestamp: u64, close_price: f64| Candle {
+            timestamp,
+            open_price: 1.0,
+            highest_price: 2.0,
+            lowest_price: 0.5,
+            close_price,
+            volume: 10.0,
+        };
+        let pair = Pair {
+            base: "BTC".to_string(),
+            quote: "USD".to_string(),
+        };
+
+        let mut legacy = ExchangeData::default();
+        legacy
+            .candles
+            .insert_many(vec![candle(60, 1.0), candle(120, 1.0)]);
+
+        let memory =
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::LegacyCandles.memory_id()));
+        let mut legacy_table: StableBTreeMap<(Exchange, Pair), Vec<u8>, Memory> =
+            StableBTreeMap::init(memory);
+        legacy_table.insert(
+            (Exchange::Okx, pair.clone()),
+            bincode::serialize(&legacy).unwrap(),
+        );
+
+        // candle fetched after the upgrade is kept, the table is already versioned
+        set_schema_version(MemoryLocation::Candles, 1);
+        let okx = ExchangeImpl::new(Exchange::Okx);
+        let mut current = ExchangeData::default();
+        current.candles.insert_many(vec![candle(120, 2.0)]);
+        okx.set_data(pair.clone(), Interval::OneMinute, StorableWrapper(current));
+
+        run_migrations();
+
+        let data = okx.get_data(pair, Interval::OneMinute).unwrap();
+        assert_eq!(
+            data.candles.get_between(0..180),
+            vec![candle(60, 1.0), candle(120, 2.0)]
+        );
+        let memory =
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::LegacyCandles.memory_id()));
+        let legacy_table: StableBTreeMap<(Exchange, Pair), Vec<u8>, Memory> =
+            StableBTreeMap::init(memory);
+        assert!(legacy_table.is_empty());
+        assert_eq!(schema_version(MemoryLocation::LegacyCandles), 1);
+    }
+}
@@ -7,6 +7,7 @@ pub use location::*;
 
 mod constants;
 mod location;
+pub mod migrations;
 
 thread_local! {
     pub static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
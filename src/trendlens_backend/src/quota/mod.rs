@@ -0,0 +1,219 @@
+use crate::{
+    memory::{Memory, MemoryLocation, MEMORY_MANAGER},
+    remote_exchanges::ExchangeErrors,
+    storable_wrapper::{StorableWrapper, Versioned},
+};
+use candid::{CandidType, Principal};
+use ic_stable_structures::{StableBTreeMap, StableCell};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+type UsageTable = StableBTreeMap<Principal, StorableWrapper<Usage>, Memory>;
+
+const DAY: u64 = 24 * 60 * 60;
+
+thread_local! {
+    static LIMITS: RefCell<StableCell<StorableWrapper<QuotaLimits>, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::QuotaLimits.memory_id())),
+            StorableWrapper(QuotaLimits::default()),
+        )
+        .expect("failed to init quota limits")
+    );
+
+    static USAGE: RefCell<UsageTable> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::QuotaUsage.memory_id())),
+        )
+    );
+}
+
+/// limits applied to every principal, outcalls made by the canister itself are not limited
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct QuotaLimits {
+    pub calls_per_window: u32,
+    /// seconds
+    pub window: u64,
+    pub cycles_per_day: u128,
+}
+
+impl Default for QuotaLimits {
+    fn default() -> Self {
+        Self {
+            calls_per_window: 60,
+            window: 60,
+            cycles_per_day: 2_000_000_000_000,
+        }
+    }
+}
+
+impl Versioned for QuotaLimits {}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+struct Usage {
+    window_start: u64,
+    calls: u32,
+    day_start: u64,
+    cycles: u128,
+}
+
+impl Versioned for Usage {}
+
+impl Usage {
+    fn new(now: u64) -> Self {
+        Self {
+            window_start: now,
+            calls: 0,
+            day_start: now,
+            cycles: 0,
+        }
+    }
+
+    /// drops counters of elapsed window and day
+    fn at(self, now: u64, limits: &QuotaLimits) -> Self {
+        let (window_start, calls) = match now >= self.window_start + limits.window {
+            true => (now, 0),
+            false => (self.window_start, self.calls),
+        };
+
+        let (day_start, cycles) = match now >= self.day_start + DAY {
+            true => (now, 0),
+            false => (self.day_start, self.cycles),
+        };
+
+        Self {
+            window_start,
+            calls,
+            day_start,
+            cycles,
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub struct Budget {
+    pub calls_left: u32,
+    pub window_resets_at: u64,
+    pub cycles_left: u128,
+    pub day_resets_at: u64,
+}
+
+pub struct Quota;
+
+impl Quota {
+    pub fn limits() -> QuotaLimits {
+        LIMITS.with_borrow(|l| l.get().0.clone())
+    }
+
+    pub fn set_limits(limits: QuotaLimits) {
+        LIMITS
+            .with_borrow_mut(|l| l.set(StorableWrapper(limits)))
+            .expect("failed to store quota limits");
+    }
+
+    fn usage(principal: &Principal, now: u64, limits: &QuotaLimits) -> Usage {
+        USAGE
+            .with_borrow(|u| u.get(principal).map(|u| u.0))
+            .unwrap_or_else(|| Usage::new(now))
+            .at(now, limits)
+    }
+
+    /// records an outcall of the principal, fails without recording when
+    /// either of the limits would be exceeded
+    pub fn charge(principal: &Principal, now: u64, cycles: u128) -> Result<(), ExchangeErrors> {
+        let limits = Self::limits();
+        let usage = Self::usage(principal, now, &limits);
+
+        if usage.calls >= limits.calls_per_window {
+            return Err(ExchangeErrors::RateLimited {
+                message: format!("{} calls per window used", limits.calls_per_window),
+                retry_at: usage.window_start + limits.window,
+            });
+        }
+
+        if usage.cycles + cycles > limits.cycles_per_day {
+            return Err(ExchangeErrors::RateLimited {
+                message: format!("{} cycles per day used", limits.cycles_per_day),
+                retry_at: usage.day_start + DAY,
+            });
+        }
+
+        USAGE.with_borrow_mut(|u| {
+            u.insert(
+                *principal,
+                StorableWrapper(Usage {
+                    calls: usage.calls + 1,
+                    cycles: usage.cycles + cycles,
+                    ..usage
+                }),
+            )
+        });
+
+        Ok(())
+    }
+
+    pub fn remaining(principal: &Principal, now: u64) -> Budget {
+        let limits = Self::limits();
+        let usage = Self::usage(principal, now, &limits);
+
+        Budget {
+            calls_left: limits.calls_per_window.saturating_sub(usage.calls),
+            window_resets_at: usage.window_start + limits.window,
+            cycles_left: limits.cycles_per_day.saturating_sub(usage.cycles),
+            day_resets_at: usage.day_start + DAY,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calls_per_window() {
+        let principal = Principal::from_text("aaaaa-aa").unwrap();
+        Quota::set_limits(QuotaLimits {
+            calls_per_window: 2,
+            window: 60,
+            cycles_per_day: 1_000,
+        });
+
+        assert!(Quota::charge(&principal, 100, 10).is_ok());
+        assert!(Quota::charge(&principal, 110, 10).is_ok());
+        assert!(matches!(
+            Quota::charge(&principal, 120, 10),
+            Err(ExchangeErrors::RateLimited { retry_at: 160, .. })
+        ));
+
+        // new window starts with the first call after the previous one elapsed
+        assert!(Quota::charge(&principal, 160, 10).is_ok());
+        assert_eq!(
+            Quota::remaining(&principal, 170),
+            Budget {
+                calls_left: 1,
+                window_resets_at: 220,
+                cycles_left: 970,
+                day_resets_at: 100 + DAY,
+            }
+        );
+    }
+
+    #[test]
+    fn test_cycles_per_day() {
+        let principal = Principal::from_text("aaaaa-aa").unwrap();
+        Quota::set_limits(QuotaLimits {
+            calls_per_window: 100,
+            window: 60,
+            cycles_per_day: 1_000,
+        });
+
+        assert!(Quota::charge(&principal, 0, 600).is_ok());
+        assert!(matches!(
+            Quota::charge(&principal, 10, 600),
+            Err(ExchangeErrors::RateLimited { .. })
+        ));
+        assert_eq!(Quota::remaining(&principal, 10).cycles_left, 400);
+
+        assert!(Quota::charge(&principal, DAY, 600).is_ok());
+    }
+}
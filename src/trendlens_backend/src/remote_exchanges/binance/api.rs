@@ -0,0 +1,259 @@
+use super::response::{
+    AccountInformation, CancelReplaceResponse, Depth, ExchangeInformation, Kline, NewOrderResponse,
+    Order, Trade,
+};
+use crate::remote_exchanges::ApiRequest;
+use ic_cdk::api::management_canister::http_request::HttpMethod;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
+
+const HOST: &str = "api.binance.com";
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KlinesRequest {
+    pub symbol: String,
+    pub interval: String,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub limit: Option<u32>,
+}
+
+impl ApiRequest for KlinesRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v3/klines";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = Vec<Kline>;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepthRequest {
+    pub symbol: String,
+    pub limit: Option<u32>,
+}
+
+impl ApiRequest for DepthRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v3/depth";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = Depth;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeInfoRequest {
+    pub symbol: Option<String>,
+}
+
+impl ApiRequest for ExchangeInfoRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v3/exchangeInfo";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = ExchangeInformation;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountRequest {
+    pub omit_zero_balances: Option<bool>,
+}
+
+impl ApiRequest for AccountRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v3/account";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = AccountInformation;
+}
+
+// binance accepts signed order parameters in the query string, which keeps
+// the signature payload identical to what is sent
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderRequest {
+    pub symbol: String,
+    pub side: String,
+    #[serde(rename = "type")]
+    pub order_type: String,
+    pub time_in_force: Option<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub quantity: Option<f64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub quote_order_qty: Option<f64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub price: Option<f64>,
+    pub new_client_order_id: Option<String>,
+}
+
+impl ApiRequest for NewOrderRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "api/v3/order";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = NewOrderResponse;
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReplaceRequest {
+    pub symbol: String,
+    pub side: String,
+    #[serde(rename = "type")]
+    pub order_type: String,
+    pub cancel_replace_mode: String,
+    pub time_in_force: Option<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub quantity: Option<f64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub price: Option<f64>,
+    pub cancel_order_id: String,
+}
+
+impl ApiRequest for CancelReplaceRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "api/v3/order/cancelReplace";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = CancelReplaceResponse;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderRequest {
+    pub symbol: String,
+    pub order_id: String,
+}
+
+impl ApiRequest for CancelOrderRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const METHOD_OVERRIDE: Option<&'static str> = Some("DELETE");
+    const URI: &'static str = "api/v3/order";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = NewOrderResponse;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOrderRequest {
+    pub symbol: String,
+    pub order_id: String,
+}
+
+impl ApiRequest for QueryOrderRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v3/order";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = Order;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyTradesRequest {
+    pub symbol: String,
+    pub order_id: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl ApiRequest for MyTradesRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v3/myTrades";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = Vec<Trade>;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenOrdersRequest {
+    pub symbol: Option<String>,
+}
+
+impl ApiRequest for OpenOrdersRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v3/openOrders";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = Vec<Order>;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllOrdersRequest {
+    pub symbol: String,
+    /// returns orders starting from this id, most recent ones when missing
+    pub order_id: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl ApiRequest for AllOrdersRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v3/allOrders";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = Vec<Order>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_klines_query_string() {
+        let request = KlinesRequest {
+            symbol: "BTCUSDT".to_string(),
+            interval: "1m".to_string(),
+            start_time: Some(1000),
+            end_time: Some(2000),
+            limit: Some(1000),
+        };
+
+        assert_eq!(
+            request.to_query_string(),
+            "symbol=BTCUSDT&interval=1m&startTime=1000&endTime=2000&limit=1000"
+        );
+    }
+
+    #[test]
+    fn test_new_order_query_string() {
+        let request = NewOrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: "BUY".to_string(),
+            order_type: "LIMIT".to_string(),
+            time_in_force: Some("GTC".to_string()),
+            quantity: Some(0.5),
+            price: Some(60000.0),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            request.to_query_string(),
+            "symbol=BTCUSDT&side=BUY&type=LIMIT&timeInForce=GTC&quantity=0.5&price=60000"
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use super::Binance;
+use crate::remote_exchanges::Authorize;
+use ic_cdk::api::management_canister::http_request::HttpHeader;
+
+pub struct BinanceAuth {
+    pub api_key: String,
+    pub signature: String,
+    /// milliseconds, must match the value used in the signed payload
+    pub timestamp: u64,
+}
+
+impl Authorize for BinanceAuth {
+    fn get_auth_headers(&self) -> Vec<HttpHeader> {
+        vec![HttpHeader {
+            name: "X-MBX-APIKEY".to_string(),
+            value: self.api_key.clone(),
+        }]
+    }
+
+    fn get_auth_query(&self) -> Option<String> {
+        Some(format!(
+            "timestamp={}&signature={}",
+            self.timestamp, self.signature
+        ))
+    }
+}
+
+impl Binance {
+    pub fn with_auth(auth: BinanceAuth) -> Self {
+        Self {
+            auth: Some(auth),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use super::request::{
+    GeneralAmendOrderRequest, GeneralCancelOrderRequest, GeneralFillsRequest,
+    GeneralOrderDetailsRequest, GeneralOrdersListRequest, GeneralPostOrderRequest, OrderSide,
+    OrderType,
+};
+use super::{response::OrdersPage, ApiRequest};
+use crate::{
+    api_client::ApiClient,
+    chain_data::ChainData,
+    exchange::{Exchange, ExchangeId},
+    interval::Interval,
+    pair::Pair,
+};
+pub use api::{
+    AccountRequest, AllOrdersRequest, MyTradesRequest, OpenOrdersRequest, QueryOrderRequest,
+};
+use api::{CancelOrderRequest, CancelReplaceRequest, NewOrderRequest};
+pub use auth::BinanceAuth;
+use response::Order as BinanceOrder;
+
+pub mod api;
+pub mod auth;
+pub mod open;
+pub mod response;
+pub mod user;
+
+pub struct Binance {
+    auth: Option<BinanceAuth>,
+    api_client: ApiClient,
+}
+
+impl Default for Binance {
+    fn default() -> Self {
+        Self {
+            auth: None,
+            api_client: ApiClient::new(Exchange::Binance),
+        }
+    }
+}
+
+impl Binance {
+    /// binance symbols are base and quote joined without separator, e.g. BTCUSDT
+    pub fn instrument_id(pair: &Pair) -> String {
+        format!("{}{}", pair.base, pair.quote).to_uppercase()
+    }
+
+    pub fn interval_string(interval: Interval) -> String {
+        match interval {
+            Interval::OneMinute => "1m",
+            Interval::FiveMinutes => "5m",
+            Interval::FifteenMinutes => "15m",
+            Interval::OneHour => "1h",
+            Interval::FourHours => "4h",
+            Interval::OneDay => "1d",
+        }
+        .to_string()
+    }
+
+    pub fn side_string(side: OrderSide) -> String {
+        match side {
+            OrderSide::Buy => "BUY",
+            OrderSide::Sell => "SELL",
+        }
+        .to_string()
+    }
+
+    /// maps general order type into binance type and time in force pair
+    pub fn order_type_string(order_type: OrderType) -> (String, Option<String>) {
+        let (order_type, time_in_force) = match order_type {
+            OrderType::Market => ("MARKET", None),
+            OrderType::Limit => ("LIMIT", Some("GTC")),
+            OrderType::PostOnly => ("LIMIT_MAKER", None),
+            OrderType::Fok => ("LIMIT", Some("FOK")),
+            OrderType::Ioc => ("LIMIT", Some("IOC")),
+        };
+
+        (order_type.to_string(), time_in_force.map(|t| t.to_string()))
+    }
+
+    pub fn new_order_request(request: &GeneralPostOrderRequest) -> NewOrderRequest {
+        let (order_type, time_in_force) = Self::order_type_string(request.order_type);
+
+        // same convention as other exchanges, market buys are sized in quote currency
+        let (quantity, quote_order_qty) = match (request.order_type, request.side) {
+            (OrderType::Market, OrderSide::Buy) => (None, Some(request.size)),
+            _ => (Some(request.size), None),
+        };
+
+        NewOrderRequest {
+            symbol: Self::instrument_id(&request.instrument_id),
+            side: Self::side_string(request.side),
+            order_type,
+            time_in_force,
+            quantity,
+            quote_order_qty,
+            price: request.order_price,
+            new_client_order_id: None,
+        }
+    }
+
+    pub fn cancel_order_request(request: &GeneralCancelOrderRequest) -> CancelOrderRequest {
+        CancelOrderRequest {
+            symbol: Self::instrument_id(&request.instrument_id),
+            order_id: request.order_id.clone(),
+        }
+    }
+
+    /// replaces resting order with limit order of the new price and size in
+    /// a single request, nothing is placed if the cancel fails
+    pub fn cancel_replace_request(request: &GeneralAmendOrderRequest) -> CancelReplaceRequest {
+        let (order_type, time_in_force) = Self::order_type_string(OrderType::Limit);
+
+        CancelReplaceRequest {
+            symbol: Self::instrument_id(&request.instrument_id),
+            side: Self::side_string(request.side),
+            order_type,
+            cancel_replace_mode: "STOP_ON_FAILURE".to_string(),
+            time_in_force,
+            quantity: Some(request.size),
+            price: Some(request.price),
+            cancel_order_id: request.order_id.clone(),
+        }
+    }
+
+    pub fn account_request() -> AccountRequest {
+        AccountRequest {
+            omit_zero_balances: None,
+        }
+    }
+
+    pub fn order_details_request(request: &GeneralOrderDetailsRequest) -> QueryOrderRequest {
+        QueryOrderRequest {
+            symbol: Self::instrument_id(&request.instrument_id),
+            order_id: request.order_id.clone(),
+        }
+    }
+
+    pub fn fills_request(request: &GeneralFillsRequest) -> MyTradesRequest {
+        MyTradesRequest {
+            symbol: Self::instrument_id(&request.instrument_id),
+            order_id: request.order_id.clone(),
+            limit: Some(100),
+        }
+    }
+
+    pub fn open_orders_request(request: &GeneralOrdersListRequest) -> OpenOrdersRequest {
+        OpenOrdersRequest {
+            symbol: Some(Self::instrument_id(&request.instrument_id)),
+        }
+    }
+
+    /// binance pages only towards newer orders, so the history is walked
+    /// from the first order of the account
+    pub fn all_orders_request(request: &GeneralOrdersListRequest) -> AllOrdersRequest {
+        AllOrdersRequest {
+            symbol: Self::instrument_id(&request.instrument_id),
+            order_id: Some(request.cursor.clone().unwrap_or("0".to_string())),
+            limit: Some(request.limit()),
+        }
+    }
+
+    /// a full page means newer orders may follow, the page is filtered only
+    /// after the cursor is taken
+    pub fn done_orders_page(orders: Vec<BinanceOrder>, limit: u32) -> OrdersPage {
+        let next_cursor = match orders.len() >= limit as usize {
+            true => orders.last().map(|o| (o.order_id + 1).to_string()),
+            false => None,
+        };
+
+        OrdersPage {
+            orders: orders
+                .into_iter()
+                .filter(|o| !o.status.is_open())
+                .map(Into::into)
+                .collect(),
+            next_cursor,
+        }
+    }
+
+    /// returns signed endpoint payload without the timestamp value, signer
+    /// has to append timestamp in milliseconds before computing the hex hmac
+    pub fn get_signature_data<R: ApiRequest>(&self, request: R) -> String {
+        let qs = request.to_query_string();
+
+        if qs.is_empty() {
+            "timestamp=".to_string()
+        } else {
+            format!("{}&timestamp=", qs)
+        }
+    }
+}
+
+impl ExchangeId for Binance {
+    fn exchange_id(&self) -> Exchange {
+        Exchange::Binance
+    }
+}
+
+impl ChainData for Binance {
+    fn key(&self) -> Exchange {
+        Exchange::Binance
+    }
+}
+
+#[cfg(test)]
+mod test_binance_helpers {
+    use super::*;
+    use crate::remote_exchanges::{okx::api::InstrumentType, request::TradeMode};
+
+    #[test]
+    fn test_binance_instrument_id() {
+        let pair = Pair {
+            base: "btc".to_string(),
+            quote: "usdt".to_string(),
+        };
+
+        assert_eq!(Binance::instrument_id(&pair), "BTCUSDT");
+    }
+
+    #[test]
+    fn test_signature_data() {
+        let binance = Binance::default();
+
+        let request = GeneralPostOrderRequest {
+            instrument_id: Pair {
+                base: "BTC".to_string(),
+                quote: "USDT".to_string(),
+            },
+            trade_mode: TradeMode::Cash,
+            side: OrderSide::Buy,
+            margin_currency: None,
+            position_side: None,
+            order_type: OrderType::Market,
+            size: 100.0,
+            order_price: None,
+        };
+
+        assert_eq!(
+            binance.get_signature_data(Binance::new_order_request(&request)),
+            "symbol=BTCUSDT&side=BUY&type=MARKET&quoteOrderQty=100&timestamp="
+        );
+
+        assert_eq!(
+            binance.get_signature_data(AccountRequest {
+                omit_zero_balances: None
+            }),
+            "timestamp="
+        );
+
+        let request = GeneralCancelOrderRequest {
+            instrument_id: Pair {
+                base: "BTC".to_string(),
+                quote: "USDT".to_string(),
+            },
+            order_id: "28".to_string(),
+        };
+
+        assert_eq!(
+            binance.get_signature_data(Binance::cancel_order_request(&request)),
+            "symbol=BTCUSDT&orderId=28&timestamp="
+        );
+    }
+
+    #[test]
+    fn test_done_orders_paging() {
+        let mut request = GeneralOrdersListRequest {
+            instrument_type: InstrumentType::Spot,
+            instrument_id: Pair {
+                base: "BTC".to_string(),
+                quote: "USDT".to_string(),
+            },
+            pending: false,
+            cursor: None,
+            limit: Some(2),
+        };
+
+        assert_eq!(
+            Binance::all_orders_request(&request).to_query_string(),
+            "symbol=BTCUSDT&orderId=0&limit=2"
+        );
+
+        let order = |id: u64, status: &str| -> BinanceOrder {
+            serde_json::from_str(&format!(
+                r#"{{"symbol":"BTCUSDT","orderId":{id},"price":"0.1","origQty":"1.0","executedQty":"0.0","cummulativeQuoteQty":"0.0","status":"{status}","timeInForce":"GTC","type":"LIMIT","side":"BUY"}}"#
+            ))
+            .unwrap()
+        };
+
+        // open orders are dropped from the page, but still move the cursor
+        let page = Binance::done_orders_page(vec![order(7, "FILLED"), order(9, "NEW")], 2);
+
+        assert_eq!(page.orders.len(), 1);
+        assert_eq!(page.next_cursor, Some("10".to_string()));
+
+        request.cursor = page.next_cursor;
+
+        assert_eq!(
+            Binance::all_orders_request(&request).to_query_string(),
+            "symbol=BTCUSDT&orderId=10&limit=2"
+        );
+
+        let page = Binance::done_orders_page(vec![order(12, "CANCELED")], 2);
+
+        assert_eq!(page.next_cursor, None);
+    }
+}
@@ -0,0 +1,125 @@
+use super::api::{DepthRequest, ExchangeInfoRequest, KlinesRequest};
+use super::auth::BinanceAuth;
+use super::response::{BinanceResponse, Depth, ExchangeInformation, Kline};
+use super::Binance;
+use crate::exchange::TimeVolume;
+use crate::interval::Interval;
+use crate::remote_exchanges::request::GeneralInstrumentsRequest;
+use crate::remote_exchanges::response::{Instrument, OrderBook as GlobalOrderBook};
+use crate::{
+    exchange::Candle,
+    pair::Pair,
+    remote_exchanges::{ExchangeErrors, OpenData},
+};
+
+const MAX_RESPONSE_CANDLES_COUNT: u32 = 1000;
+
+// market data endpoints are public, signing them would only add unexpected
+// timestamp and signature parameters to the query
+impl Binance {
+    async fn fetch_klines(
+        &self,
+        pair: &Pair,
+        range: std::ops::Range<u64>,
+        interval: Interval,
+    ) -> Result<Vec<Kline>, ExchangeErrors> {
+        let mut current = range.start;
+        let mut klines = vec![];
+
+        log!("Binance: fetching range {:?}", range);
+
+        while current < range.end {
+            let end = u64::min(
+                current + interval.seconds() * MAX_RESPONSE_CANDLES_COUNT as u64,
+                range.end,
+            );
+
+            log!("Binance: fetching from {} to {}", current, end);
+
+            let request = KlinesRequest {
+                symbol: Binance::instrument_id(pair),
+                interval: Binance::interval_string(interval),
+                start_time: Some(current * 1000),
+                end_time: Some(end * 1000),
+                limit: Some(MAX_RESPONSE_CANDLES_COUNT),
+            };
+
+            let response = self
+                .api_client
+                .call::<BinanceResponse<Vec<Kline>>, KlinesRequest, BinanceAuth>(request, None)
+                .await?;
+
+            klines.extend(response);
+
+            current = end + 1;
+        }
+
+        Ok(klines)
+    }
+}
+
+#[async_trait::async_trait]
+impl OpenData for Binance {
+    async fn fetch_candles(
+        &self,
+        pair: &Pair,
+        range: std::ops::Range<u64>,
+        interval: Interval,
+    ) -> Result<Vec<Candle>, ExchangeErrors> {
+        let klines = self.fetch_klines(pair, range, interval).await?;
+
+        Ok(klines.into_iter().map(|k| k.into()).collect())
+    }
+
+    /// binance klines carry taker buy volume, the rest of volume are taker sells
+    async fn get_taker_volume(
+        &self,
+        pair: &Pair,
+        range: std::ops::Range<u64>,
+    ) -> Result<Vec<TimeVolume>, ExchangeErrors> {
+        let klines = self
+            .fetch_klines(pair, range, Interval::FiveMinutes)
+            .await?;
+
+        Ok(klines.into_iter().map(|k| k.into()).collect())
+    }
+
+    async fn get_public_instruments(
+        &self,
+        _request: GeneralInstrumentsRequest,
+    ) -> Result<Vec<Instrument>, ExchangeErrors> {
+        let request = ExchangeInfoRequest { symbol: None };
+
+        let response = self
+            .api_client
+            .call::<BinanceResponse<ExchangeInformation>, ExchangeInfoRequest, BinanceAuth>(
+                request, None,
+            )
+            .await?;
+
+        Ok(response
+            .symbols
+            .into_iter()
+            .filter(|s| s.status == "TRADING")
+            .map(Into::into)
+            .collect())
+    }
+
+    async fn get_orderbook(
+        &self,
+        pair: &Pair,
+        size: u32,
+    ) -> Result<GlobalOrderBook, ExchangeErrors> {
+        let request = DepthRequest {
+            symbol: Binance::instrument_id(pair),
+            limit: Some(size),
+        };
+
+        let response = self
+            .api_client
+            .call::<BinanceResponse<Depth>, DepthRequest, BinanceAuth>(request, None)
+            .await?;
+
+        Ok(response.into())
+    }
+}
@@ -0,0 +1,435 @@
+use crate::{
+    exchange::{Candle, TimeVolume},
+    pair::Pair,
+    remote_exchanges::{
+        okx::api::InstrumentType,
+        request::{OrderSide, OrderType},
+        response::{
+            ApiResponseWrapper, Balance, BidAsk as GlobalBidAsk, Fill, Instrument, Liquidity,
+            Order as GlobalOrder, OrderBook as GlobalOrderBook, OrderData,
+            OrderStatus as GlobalOrderStatus, ReportedOrderType,
+        },
+        ExchangeErrors,
+    },
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct BinanceResponse<T>(pub T);
+
+impl<R: DeserializeOwned> ApiResponseWrapper<R> for BinanceResponse<R> {
+    fn extract_response(self) -> Result<R, ExchangeErrors> {
+        Ok(self.0)
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Kline(
+    pub u64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    pub u64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    pub u64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    pub String,
+);
+
+impl From<Kline> for Candle {
+    fn from(value: Kline) -> Self {
+        Candle {
+            timestamp: value.0 / 1000,
+            open_price: value.1,
+            highest_price: value.2,
+            lowest_price: value.3,
+            close_price: value.4,
+            volume: value.5,
+        }
+    }
+}
+
+// index 9 holds taker buy base asset volume
+impl From<Kline> for TimeVolume {
+    fn from(value: Kline) -> Self {
+        TimeVolume::with_split(value.0 / 1000, value.9, value.5 - value.9)
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct BidAsk(
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+);
+
+impl From<BidAsk> for GlobalBidAsk {
+    fn from(value: BidAsk) -> Self {
+        GlobalBidAsk {
+            price: value.0,
+            size: value.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Depth {
+    #[serde(rename = "lastUpdateId")]
+    pub last_update_id: u64,
+    pub bids: Vec<BidAsk>,
+    pub asks: Vec<BidAsk>,
+}
+
+impl From<Depth> for GlobalOrderBook {
+    fn from(value: Depth) -> Self {
+        GlobalOrderBook {
+            asks: value.asks.into_iter().map(|x| x.into()).collect(),
+            bids: value.bids.into_iter().map(|x| x.into()).collect(),
+            sequence: value.last_update_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExchangeInformation {
+    pub symbols: Vec<Symbol>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Symbol {
+    pub status: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+}
+
+impl From<Symbol> for Instrument {
+    fn from(value: Symbol) -> Self {
+        Instrument {
+            instrument_id: Pair {
+                base: value.base_asset,
+                quote: value.quote_asset,
+            },
+            instrument_type: InstrumentType::Spot,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInformation {
+    pub balances: Vec<AssetBalance>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssetBalance {
+    pub asset: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub free: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub locked: f64,
+}
+
+impl From<AssetBalance> for Balance {
+    fn from(value: AssetBalance) -> Self {
+        Balance {
+            currency: value.asset,
+            balance: (value.free + value.locked).to_string(),
+            available: value.free.to_string(),
+            hold: value.locked.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderResponse {
+    pub order_id: u64,
+    pub status: Option<String>,
+}
+
+impl From<NewOrderResponse> for OrderData {
+    fn from(value: NewOrderResponse) -> Self {
+        OrderData {
+            id: value.order_id.to_string(),
+            message: value.status.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReplaceResponse {
+    pub cancel_result: String,
+    pub new_order_result: String,
+    pub new_order_response: Option<NewOrderResponse>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub symbol: String,
+    pub order_id: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub orig_qty: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub executed_qty: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub cummulative_quote_qty: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub status: OrderStatus,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub order_type: String,
+    pub side: Side,
+}
+
+impl Order {
+    /// binance expresses ioc and fok as time in force of limit orders
+    pub fn order_type(&self) -> ReportedOrderType {
+        let order_type = match (self.order_type.as_str(), self.time_in_force.as_str()) {
+            ("MARKET", _) => OrderType::Market,
+            ("LIMIT_MAKER", _) => OrderType::PostOnly,
+            ("LIMIT", "IOC") => OrderType::Ioc,
+            ("LIMIT", "FOK") => OrderType::Fok,
+            ("LIMIT", _) => OrderType::Limit,
+            (other, _) => return ReportedOrderType::Other(other.to_string()),
+        };
+
+        ReportedOrderType::Common(order_type)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl From<Side> for OrderSide {
+    fn from(value: Side) -> Self {
+        match value {
+            Side::Buy => OrderSide::Buy,
+            Side::Sell => OrderSide::Sell,
+        }
+    }
+}
+
+impl From<Order> for GlobalOrder {
+    fn from(value: Order) -> Self {
+        GlobalOrder {
+            instrument_type: InstrumentType::Spot.to_string(),
+            order_type: value.order_type(),
+            instrument_id: value.symbol,
+            order_id: value.order_id.to_string(),
+            state: value.status.into(),
+            price: value.price,
+            size: value.orig_qty,
+            side: value.side.into(),
+            trade_mode: value.time_in_force,
+            accumulated_fill_quantity: value.executed_qty,
+            average_price: (value.executed_qty > 0.0)
+                .then(|| value.cummulative_quote_qty / value.executed_qty),
+            // commissions are reported only on trades
+            fee: None,
+            fee_currency: None,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub symbol: String,
+    pub id: u64,
+    pub order_id: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub qty: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub commission: f64,
+    pub commission_asset: String,
+    pub time: u64,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+}
+
+impl From<Trade> for Fill {
+    fn from(value: Trade) -> Self {
+        Fill {
+            instrument_id: value.symbol,
+            order_id: value.order_id.to_string(),
+            trade_id: value.id.to_string(),
+            side: match value.is_buyer {
+                true => OrderSide::Buy,
+                false => OrderSide::Sell,
+            },
+            price: value.price,
+            size: value.qty,
+            fee: value.commission,
+            fee_currency: value.commission_asset,
+            liquidity: Some(match value.is_maker {
+                true => Liquidity::Maker,
+                false => Liquidity::Taker,
+            }),
+            timestamp: value.time / 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub enum OrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    PendingCancel,
+    Rejected,
+    Expired,
+    ExpiredInMatch,
+}
+
+impl From<OrderStatus> for GlobalOrderStatus {
+    fn from(value: OrderStatus) -> Self {
+        match value {
+            // cancellation is not confirmed yet, the order can still fill
+            OrderStatus::New | OrderStatus::PendingCancel => GlobalOrderStatus::Open,
+            OrderStatus::PartiallyFilled => GlobalOrderStatus::PartiallyFilled,
+            OrderStatus::Filled => GlobalOrderStatus::Filled,
+            OrderStatus::Canceled => GlobalOrderStatus::Cancelled,
+            OrderStatus::Rejected => GlobalOrderStatus::Rejected,
+            OrderStatus::Expired | OrderStatus::ExpiredInMatch => GlobalOrderStatus::Expired,
+        }
+    }
+}
+
+impl OrderStatus {
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::New | OrderStatus::PartiallyFilled | OrderStatus::PendingCancel
+        )
+    }
+}
+
+impl std::str::FromStr for OrderStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NEW" => Ok(OrderStatus::New),
+            "PARTIALLY_FILLED" => Ok(OrderStatus::PartiallyFilled),
+            "FILLED" => Ok(OrderStatus::Filled),
+            "CANCELED" => Ok(OrderStatus::Canceled),
+            "PENDING_CANCEL" => Ok(OrderStatus::PendingCancel),
+            "REJECTED" => Ok(OrderStatus::Rejected),
+            "EXPIRED" => Ok(OrderStatus::Expired),
+            "EXPIRED_IN_MATCH" => Ok(OrderStatus::ExpiredInMatch),
+            _ => Err(format!("Unknown order status: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            OrderStatus::New => write!(f, "NEW"),
+            OrderStatus::PartiallyFilled => write!(f, "PARTIALLY_FILLED"),
+            OrderStatus::Filled => write!(f, "FILLED"),
+            OrderStatus::Canceled => write!(f, "CANCELED"),
+            OrderStatus::PendingCancel => write!(f, "PENDING_CANCEL"),
+            OrderStatus::Rejected => write!(f, "REJECTED"),
+            OrderStatus::Expired => write!(f, "EXPIRED"),
+            OrderStatus::ExpiredInMatch => write!(f, "EXPIRED_IN_MATCH"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_klines() {
+        let response = r#"[[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0"]]"#;
+        let response: BinanceResponse<Vec<Kline>> = serde_json::from_str(response).unwrap();
+
+        let candle: Candle = response.0[0].clone().into();
+
+        assert_eq!(candle.timestamp, 1499040000);
+        assert_eq!(candle.open_price, 0.0163479);
+        assert_eq!(candle.highest_price, 0.8);
+        assert_eq!(candle.lowest_price, 0.015758);
+        assert_eq!(candle.close_price, 0.015771);
+        assert_eq!(candle.volume, 148976.11427815);
+    }
+
+    #[test]
+    fn test_kline_taker_volume() {
+        let response = r#"[[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","100.0",1499644799999,"2434.19055334",308,"75.0","28.46694368","0"]]"#;
+        let response: BinanceResponse<Vec<Kline>> = serde_json::from_str(response).unwrap();
+
+        let volume: TimeVolume = response.0[0].clone().into();
+
+        assert_eq!(volume.volume, 100.0);
+        assert_eq!(volume.buy_volume, Some(75.0));
+        assert_eq!(volume.sell_volume, Some(25.0));
+        assert_eq!(volume.imbalance, Some(0.5));
+    }
+
+    #[test]
+    fn test_deserialize_depth() {
+        let response = r#"{"lastUpdateId":1027024,"bids":[["4.00000000","431.00000000"]],"asks":[["4.00000200","12.00000000"]]}"#;
+        let depth: Depth = serde_json::from_str(response).unwrap();
+        let orderbook: GlobalOrderBook = depth.into();
+
+        assert_eq!(orderbook.sequence, 1027024);
+        assert_eq!(orderbook.bids[0].price, 4.0);
+        assert_eq!(orderbook.bids[0].size, 431.0);
+        assert_eq!(orderbook.asks[0].price, 4.000002);
+    }
+
+    #[test]
+    fn test_deserialize_order() {
+        let response = r#"{"symbol":"LTCBTC","orderId":1,"orderListId":-1,"clientOrderId":"myOrder1","price":"0.1","origQty":"1.0","executedQty":"0.0","cummulativeQuoteQty":"0.0","status":"NEW","timeInForce":"GTC","type":"LIMIT","side":"BUY","stopPrice":"0.0","icebergQty":"0.0","time":1499827319559,"updateTime":1499827319559,"isWorking":true,"origQuoteOrderQty":"0.000000"}"#;
+        let order: Order = serde_json::from_str(response).unwrap();
+
+        assert!(order.status.is_open());
+
+        let order: GlobalOrder = order.into();
+
+        assert_eq!(order.order_id, "1");
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(
+            order.order_type,
+            ReportedOrderType::Common(OrderType::Limit)
+        );
+        assert_eq!(order.state, GlobalOrderStatus::Open);
+    }
+
+    #[test]
+    fn test_deserialize_trade() {
+        let response = r#"[{"symbol":"BNBBTC","id":28457,"orderId":100234,"orderListId":-1,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","commission":"10.10000000","commissionAsset":"BNB","time":1499865549590,"isBuyer":true,"isMaker":false,"isBestMatch":true}]"#;
+        let trades: Vec<Trade> = serde_json::from_str(response).unwrap();
+
+        let fill: Fill = trades[0].clone().into();
+
+        assert_eq!(fill.order_id, "100234");
+        assert_eq!(fill.side, OrderSide::Buy);
+        assert_eq!(fill.fee_currency, "BNB");
+        assert_eq!(fill.liquidity, Some(Liquidity::Taker));
+        assert_eq!(fill.timestamp, 1499865549);
+    }
+}
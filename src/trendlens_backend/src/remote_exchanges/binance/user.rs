@@ -0,0 +1,201 @@
+use super::{
+    api::{
+        AccountRequest, AllOrdersRequest, CancelOrderRequest, CancelReplaceRequest,
+        MyTradesRequest, NewOrderRequest, OpenOrdersRequest, QueryOrderRequest,
+    },
+    auth::BinanceAuth,
+    response::{
+        AccountInformation, BinanceResponse, CancelReplaceResponse, NewOrderResponse,
+        Order as BinanceOrder, Trade,
+    },
+    Binance,
+};
+use crate::{
+    remote_exchanges::{
+        request::{
+            GeneralAmendOrderRequest, GeneralBalanceRequest, GeneralCancelOrderRequest,
+            GeneralFillsRequest, GeneralInstrumentsRequest, GeneralOrderDetailsRequest,
+            GeneralOrdersListRequest, GeneralPostOrderRequest,
+        },
+        response::{AmendedOrder, OrdersPage},
+        ExchangeErrors, OpenData, UserData,
+    },
+    request_store::request::Response,
+};
+
+#[async_trait::async_trait]
+impl UserData for Binance {
+    async fn get_instruments(
+        &self,
+        req: GeneralInstrumentsRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        Ok(Response::Instruments(
+            self.get_public_instruments(req).await?,
+        ))
+    }
+
+    async fn get_balance(
+        &self,
+        request: GeneralBalanceRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::account_request();
+
+        let account = self
+            .api_client
+            .call::<BinanceResponse<AccountInformation>, AccountRequest, BinanceAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Balances(
+            account
+                .balances
+                .into_iter()
+                .filter(|b| match request.currency {
+                    Some(ref currencies) => currencies.contains(&b.asset),
+                    None => true,
+                })
+                .map(Into::into)
+                .collect(),
+        ))
+    }
+
+    async fn post_order(
+        &self,
+        request: GeneralPostOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::new_order_request(&request);
+
+        let order_response = self
+            .api_client
+            .call::<BinanceResponse<NewOrderResponse>, NewOrderRequest, BinanceAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Order(order_response.into()))
+    }
+
+    async fn cancel_order(
+        &self,
+        request: GeneralCancelOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::cancel_order_request(&request);
+
+        let cancel_response = self
+            .api_client
+            .call::<BinanceResponse<NewOrderResponse>, CancelOrderRequest, BinanceAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Cancelled(cancel_response.into()))
+    }
+
+    async fn amend_order(
+        &self,
+        request: GeneralAmendOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::cancel_replace_request(&request);
+
+        let replace_response = self
+            .api_client
+            .call::<BinanceResponse<CancelReplaceResponse>, CancelReplaceRequest, BinanceAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        let order = replace_response
+            .new_order_response
+            .ok_or(ExchangeErrors::RequestRejected {
+                message: format!(
+                    "cancel {}, new order {}",
+                    replace_response.cancel_result, replace_response.new_order_result
+                ),
+            })?;
+
+        Ok(Response::Amended(AmendedOrder {
+            id: order.order_id.to_string(),
+            emulated: false,
+            message: order.status.unwrap_or_default(),
+        }))
+    }
+
+    async fn get_order_details(
+        &self,
+        request: GeneralOrderDetailsRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::order_details_request(&request);
+
+        let order = self
+            .api_client
+            .call::<BinanceResponse<BinanceOrder>, QueryOrderRequest, BinanceAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::OrderDetails(order.into()))
+    }
+
+    async fn get_fills(&self, request: GeneralFillsRequest) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::fills_request(&request);
+
+        let trades = self
+            .api_client
+            .call::<BinanceResponse<Vec<Trade>>, MyTradesRequest, BinanceAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Fills(
+            trades.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    async fn get_pending_orders(
+        &self,
+        request: GeneralOrdersListRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::open_orders_request(&request);
+
+        let order_response = self
+            .api_client
+            .call::<BinanceResponse<Vec<BinanceOrder>>, OpenOrdersRequest, BinanceAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::OrdersInfo(OrdersPage {
+            orders: order_response.into_iter().map(Into::into).collect(),
+            next_cursor: None,
+        }))
+    }
+
+    async fn get_done_orders(
+        &self,
+        request: GeneralOrdersListRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::all_orders_request(&request);
+
+        let order_response = self
+            .api_client
+            .call::<BinanceResponse<Vec<BinanceOrder>>, AllOrdersRequest, BinanceAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        // allOrders returns every status, keep only the finished ones
+        Ok(Response::OrdersInfo(Self::done_orders_page(
+            order_response,
+            request.limit(),
+        )))
+    }
+}
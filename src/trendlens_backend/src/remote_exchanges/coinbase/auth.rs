@@ -36,6 +36,17 @@ impl Authorize for CoinbaseAuth {
     }
 }
 
+impl CoinbaseAuth {
+    pub fn with_signature(&self, signature: &str) -> Self {
+        Self {
+            api_key: self.api_key.clone(),
+            passphrase: self.passphrase.clone(),
+            signature: signature.to_string(),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
 impl Coinbase {
     pub fn with_auth(auth: CoinbaseAuth) -> Self {
         Self {
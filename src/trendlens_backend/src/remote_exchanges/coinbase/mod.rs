@@ -1,3 +1,8 @@
+use super::request::{
+    GeneralAmendOrderRequest, GeneralCancelOrderRequest, GeneralFillsRequest,
+    GeneralOrderDetailsRequest, GeneralOrdersListRequest, GeneralPostOrderRequest, OrderSide,
+    OrderType,
+};
 use super::response::OrderBook as GlobalOrderBook;
 use super::{ApiRequest, ExchangeErrors, OpenData};
 use crate::exchange::TimeVolume;
@@ -5,63 +10,171 @@ use crate::{
     api_client::ApiClient,
     chain_data::ChainData,
     exchange::{Candle, Exchange, ExchangeId},
+    interval::Interval,
     pair::Pair,
 };
 pub use auth::CoinbaseAuth;
-use ic_cdk::api::management_canister::http_request::HttpMethod;
+pub use request::CancelOrderRequest as CoinbaseCancelOrderRequest;
+pub use request::FillsRequest as CoinbaseFillsRequest;
+pub use request::GetOrderRequest as CoinbaseGetOrderRequest;
 pub use request::GetProfileAccountsRequest;
 pub use request::OrdersRequest as CoinbaseOrdersRequest;
 pub use request::PostOrderBody;
 pub use request::Statuses;
-use request::{GetAllPairsRequest, GetOrderbookRequest, GetProductCandles};
-use response::{CoinbaseCandle, CoinbaseResponse, ConcreteInstrument, OrderBook};
+use request::{GetAllPairsRequest, GetOrderbookRequest, GetProductCandles, GetProductTrades};
+use response::{
+    CoinbaseCandle, CoinbaseResponse, ConcreteInstrument, OrderBook, OrderStatus, Trade,
+};
+use std::collections::BTreeMap;
 
 const MAX_RESPONSE_CANDLES_COUNT: u32 = 300;
+const MAX_RESPONSE_TRADES_COUNT: u32 = 1000;
+// trades can only be paged from the newest ones, limit outcalls per pull
+const MAX_TRADES_PAGES: u32 = 10;
+
+/// separates signature data, and signatures, of instructions which are
+/// executed as multiple signed requests
+pub const SIGNATURE_SEPARATOR: char = '\n';
+
+/// response header with the cursor of older results
+pub const CURSOR_HEADER: &str = "cb-after";
 
 mod auth;
 mod request;
 mod response;
 mod user;
 
-#[derive(Default)]
 pub struct Coinbase {
     api_client: ApiClient,
     auth: Option<auth::CoinbaseAuth>,
 }
 
+impl Default for Coinbase {
+    fn default() -> Self {
+        Self {
+            api_client: ApiClient::new(Exchange::Coinbase),
+            auth: None,
+        }
+    }
+}
+
 impl Coinbase {
-    pub fn interval_string(interval: u32) -> String {
+    /// gets granularity in seconds, coinbase has no 4 hour candles
+    pub fn interval_string(interval: Interval) -> Option<String> {
         match interval {
-            0..=60 => "60",
-            61..=u32::MAX => "300",
+            Interval::OneMinute => Some("60"),
+            Interval::FiveMinutes => Some("300"),
+            Interval::FifteenMinutes => Some("900"),
+            Interval::OneHour => Some("3600"),
+            Interval::FourHours => None,
+            Interval::OneDay => Some("86400"),
         }
-        .to_string()
+        .map(|granularity| granularity.to_string())
     }
 
-    pub fn get_signature_data<R: ApiRequest>(&self, request: R) -> String {
-        let (qs, body) = if R::BODY {
-            ("".to_string(), request.to_body())
-        } else {
-            let qs = request.to_query_string();
-
-            let qs = if qs == "" {
-                "".to_string()
-            } else {
-                format!("?{}", qs)
-            };
+    /// sums trades into taker buy and sell volume per interval bucket
+    pub fn bucket_trades(trades: Vec<Trade>, interval: Interval) -> Vec<TimeVolume> {
+        let mut buckets: BTreeMap<u64, (f64, f64)> = BTreeMap::new();
+
+        for trade in trades {
+            let timestamp = trade.timestamp();
+            let (buy, sell) = buckets
+                .entry(timestamp - timestamp % interval.seconds())
+                .or_default();
+
+            // coinbase reports the maker side, a sell maker was hit by a taker buy
+            match trade.side {
+                OrderSide::Sell => *buy += trade.size,
+                OrderSide::Buy => *sell += trade.size,
+            }
+        }
+
+        buckets
+            .into_iter()
+            .map(|(timestamp, (buy, sell))| TimeVolume::with_split(timestamp, buy, sell))
+            .collect()
+    }
+
+    // requests below are both signed and sent, build them only here
+
+    /// market buys are sized in quote currency
+    pub fn post_order_request(request: &GeneralPostOrderRequest) -> PostOrderBody {
+        PostOrderBody {
+            order_type: request.order_type.into(),
+            size: match request.side {
+                OrderSide::Buy => None,
+                OrderSide::Sell => Some(request.size),
+            },
+            side: request.side,
+            funds: match request.side {
+                OrderSide::Buy => Some(request.size),
+                OrderSide::Sell => None,
+            },
+            price: request.order_price,
+            product_id: request.instrument_id.to_string(),
+        }
+    }
 
-            (qs, "".to_string())
+    pub fn cancel_order_request(request: &GeneralCancelOrderRequest) -> CoinbaseCancelOrderRequest {
+        CoinbaseCancelOrderRequest {
+            order_id: request.order_id.clone(),
+            product_id: Some(request.instrument_id.to_string()),
+        }
+    }
+
+    pub fn order_details_request(request: &GeneralOrderDetailsRequest) -> CoinbaseGetOrderRequest {
+        CoinbaseGetOrderRequest {
+            order_id: request.order_id.clone(),
+        }
+    }
+
+    pub fn fills_request(request: &GeneralFillsRequest) -> CoinbaseFillsRequest {
+        CoinbaseFillsRequest {
+            order_id: request.order_id.clone(),
+            product_id: Some(request.instrument_id.to_string()),
+            limit: Some(100),
+        }
+    }
+
+    /// coinbase has no order amendment, it is emulated by cancelling the order
+    /// and placing a limit order with the new price and size
+    pub fn amend_order_requests(
+        request: &GeneralAmendOrderRequest,
+    ) -> (CoinbaseCancelOrderRequest, PostOrderBody) {
+        let cancel_request = Self::cancel_order_request(&GeneralCancelOrderRequest {
+            instrument_id: request.instrument_id.clone(),
+            order_id: request.order_id.clone(),
+        });
+
+        let post_request = PostOrderBody {
+            product_id: request.instrument_id.to_string(),
+            size: Some(request.size),
+            price: Some(request.price),
+            funds: None,
+            order_type: OrderType::Limit.into(),
+            side: request.side,
         };
 
-        let method_str = if R::METHOD == HttpMethod::GET {
-            "GET"
-        } else {
-            "POST"
+        (cancel_request, post_request)
+    }
+
+    pub fn orders_request(request: &GeneralOrdersListRequest) -> CoinbaseOrdersRequest {
+        let statuses = match request.pending {
+            true => [OrderStatus::Open, OrderStatus::Pending],
+            false => [OrderStatus::Done, OrderStatus::Rejected],
         };
 
-        let api_url = format!("/{}{}", R::URI, qs);
+        CoinbaseOrdersRequest {
+            product_id: Some(request.instrument_id.to_string()),
+            market_type: Some(request.instrument_type.to_string()),
+            limit: request.limit() as u64,
+            after: request.cursor.clone(),
+            status: Some(Statuses(statuses.iter().map(|s| s.to_string()).collect())),
+        }
+    }
 
-        format!("{}{}{}", method_str, api_url, body).to_string()
+    pub fn get_signature_data<R: ApiRequest>(&self, request: R) -> String {
+        request.parts().signature_payload()
     }
 }
 
@@ -71,21 +184,26 @@ impl OpenData for Coinbase {
         &self,
         pair: &Pair,
         range: std::ops::Range<u64>,
-        interval: u32,
+        interval: Interval,
     ) -> Result<Vec<Candle>, super::ExchangeErrors> {
+        let granularity =
+            Coinbase::interval_string(interval).ok_or(ExchangeErrors::UnsupportedInterval)?;
         let mut current = range.start;
         let mut responses = vec![];
 
-        ic_cdk::println!("Coinbase: fetching range {:?}", range);
+        log!("Coinbase: fetching range {:?}", range);
 
-        while current <= range.end {
-            let end = u64::min(current + (interval * MAX_RESPONSE_CANDLES_COUNT) as u64, range.end);
+        while current < range.end {
+            let end = u64::min(
+                current + interval.seconds() * MAX_RESPONSE_CANDLES_COUNT as u64,
+                range.end,
+            );
 
-            ic_cdk::println!("Coinbase: fetching from {} to {}", current, end);
+            log!("Coinbase: fetching from {} to {}", current, end);
 
             let candle_request = GetProductCandles {
                 product_id: pair.to_string(),
-                granularity: Some(Coinbase::interval_string(interval)),
+                granularity: Some(granularity.clone()),
                 start: Some(current.to_string()),
                 end: Some(end.to_string()),
             };
@@ -102,7 +220,7 @@ impl OpenData for Coinbase {
 
             current = end + 1;
 
-            ic_cdk::println!("c {} e {}", current, range.end)
+            log!("c {} e {}", current, range.end)
         }
 
         Ok(responses
@@ -116,15 +234,54 @@ impl OpenData for Coinbase {
         pair: &Pair,
         range: std::ops::Range<u64>,
     ) -> Result<Vec<TimeVolume>, ExchangeErrors> {
-        self.fetch_candles(pair, range, 300).await.map(|candles| {
-            candles
-                .into_iter()
-                .map(|candle| TimeVolume {
-                    timestamp: candle.timestamp,
-                    volume: candle.volume,
-                })
-                .collect()
-        })
+        let mut trades = vec![];
+        let mut after = None;
+        let mut covered_from = range.end;
+
+        for _ in 0..MAX_TRADES_PAGES {
+            let request = GetProductTrades {
+                product_id: pair.to_string(),
+                limit: Some(MAX_RESPONSE_TRADES_COUNT),
+                after,
+            };
+
+            let page = self
+                .api_client
+                .call::<CoinbaseResponse<Vec<Trade>>, GetProductTrades, CoinbaseAuth>(request, None)
+                .await?;
+
+            // trades come newest first
+            let Some(oldest) = page.last() else {
+                covered_from = range.start;
+                break;
+            };
+            covered_from = oldest.timestamp();
+            after = Some(oldest.trade_id.to_string());
+
+            trades.extend(page.into_iter().filter(|t| range.contains(&t.timestamp())));
+
+            if covered_from < range.start {
+                break;
+            }
+        }
+
+        // volumes are pulled forward from the last stored one, so a range busier than
+        // the page budget keeps the fully covered buckets and the next pull moves on
+        if covered_from > range.start {
+            let bucket = Interval::FiveMinutes.seconds();
+            let first_full_bucket = (covered_from / bucket + 1) * bucket;
+
+            log!(
+                "Coinbase: trades of {} are covered from {}, volumes before {} are skipped",
+                pair.to_string(),
+                covered_from,
+                first_full_bucket
+            );
+
+            trades.retain(|t| t.timestamp() >= first_full_bucket);
+        }
+
+        Ok(Coinbase::bucket_trades(trades, Interval::FiveMinutes))
     }
 
     async fn get_public_instruments(
@@ -177,3 +334,146 @@ impl ChainData for Coinbase {
         Exchange::Coinbase
     }
 }
+
+#[cfg(test)]
+mod test_coinbase_helpers {
+    use super::*;
+    use crate::{
+        api_client::transport::{load_traffic, RecordedCall, TransportKind},
+        remote_exchanges::okx::api::InstrumentType,
+    };
+    use futures::executor::block_on;
+    use ic_cdk::api::management_canister::http_request::{HttpMethod, HttpResponse};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_bucket_trades() {
+        let trades = r#"[
+            {"time":"2024-05-01T12:04:59Z","trade_id":4,"size":"1.0","side":"buy"},
+            {"time":"2024-05-01T12:01:00Z","trade_id":3,"size":"3.0","side":"sell"},
+            {"time":"2024-05-01T12:00:00Z","trade_id":2,"size":"1.0","side":"sell"},
+            {"time":"2024-05-01T11:59:59Z","trade_id":1,"size":"2.0","side":"buy"}
+        ]"#;
+        let trades: Vec<Trade> = serde_json::from_str(trades).unwrap();
+
+        let volumes = Coinbase::bucket_trades(trades, Interval::FiveMinutes);
+
+        assert_eq!(
+            volumes,
+            vec![
+                TimeVolume::with_split(1714564500, 0.0, 2.0),
+                TimeVolume::with_split(1714564800, 4.0, 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cancel_order_signature_data() {
+        let request = CoinbaseCancelOrderRequest {
+            order_id: "d0c5340b".to_string(),
+            product_id: Some("BTC-USD".to_string()),
+        };
+
+        assert_eq!(
+            Coinbase::default().get_signature_data(request),
+            "DELETE/orders/d0c5340b?product_id=BTC-USD"
+        );
+    }
+
+    #[test]
+    fn test_amend_order_signature_data() {
+        let request = GeneralAmendOrderRequest {
+            instrument_id: Pair {
+                base: "BTC".to_string(),
+                quote: "USD".to_string(),
+            },
+            order_id: "d0c5340b".to_string(),
+            side: OrderSide::Buy,
+            size: 0.01,
+            price: 10000.0,
+        };
+
+        let coinbase = Coinbase::default();
+        let (cancel_request, post_request) = Coinbase::amend_order_requests(&request);
+
+        assert_eq!(
+            coinbase.get_signature_data(cancel_request),
+            "DELETE/orders/d0c5340b?product_id=BTC-USD"
+        );
+        assert_eq!(
+            coinbase.get_signature_data(post_request),
+            r#"POST/orders{"product_id":"BTC-USD","size":"0.01","price":"10000","type":"limit","side":"buy"}"#
+        );
+    }
+
+    #[test]
+    fn test_orders_signature_data() {
+        let request = GeneralOrdersListRequest {
+            instrument_type: InstrumentType::Spot,
+            instrument_id: Pair {
+                base: "BTC".to_string(),
+                quote: "USD".to_string(),
+            },
+            pending: true,
+            cursor: Some("2024-01-01T00:00:00Z".to_string()),
+            limit: None,
+        };
+
+        let coinbase = Coinbase::default();
+
+        assert_eq!(
+            coinbase.get_signature_data(Coinbase::orders_request(&request)),
+            "GET/orders?product_id=BTC-USD&market_type=SPOT&limit=100&after=2024-01-01T00%3A00%3A00Z&status=open&status=pending"
+        );
+    }
+
+    #[test]
+    fn test_taker_volume_beyond_page_limit() {
+        TransportKind::set(Exchange::Coinbase, TransportKind::Replay);
+
+        let url = ApiClient::http_request::<_, CoinbaseAuth>(
+            &GetProductTrades {
+                product_id: "btc-usd".to_string(),
+                limit: None,
+                after: None,
+            },
+            None,
+        )
+        .url;
+        // one trade a minute, newest first
+        let page = |id: u64| {
+            RecordedCall {
+            method: HttpMethod::GET,
+            url: url.clone(),
+            response: HttpResponse {
+                status: 200u32.into(),
+                headers: vec![],
+                body: format!(
+                    r#"[{{"time":"2024-05-01T12:{:02}:00Z","trade_id":{},"size":"1.0","side":"buy"}}]"#,
+                    id, id
+                )
+                .into_bytes(),
+            },
+        }
+        };
+        load_traffic(
+            (0..MAX_TRADES_PAGES as u64)
+                .rev()
+                .map(|id| page(id + 20))
+                .collect(),
+        );
+
+        let start = 1714564800; // 2024-05-01T12:00:00Z
+        let volumes = block_on(
+            Coinbase::default()
+                .get_taker_volume(&Pair::from_str("btc-usd").unwrap(), start..start + 3600),
+        )
+        .unwrap();
+
+        // pages reach back to 12:20 only, its bucket may miss trades of the same second
+        assert_eq!(
+            volumes,
+            vec![TimeVolume::with_split(start + 25 * 60, 0.0, 5.0)]
+        );
+    }
+}
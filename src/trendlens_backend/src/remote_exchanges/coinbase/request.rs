@@ -151,6 +151,93 @@ impl ApiRequest for PostOrderBody {
     type Response = response::OrderResponse;
 }
 
+#[derive(Deserialize)]
+pub struct CancelOrderRequest {
+    pub order_id: String,
+    pub product_id: Option<String>,
+}
+
+impl Serialize for CancelOrderRequest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("CancelOrderRequest", 2)?;
+
+        if self.product_id.is_some() {
+            state.serialize_field("product_id", &self.product_id)?;
+        }
+
+        if is_json_serializer::<S>() {
+            state.serialize_field("order_id", &self.order_id)?;
+        }
+
+        state.end()
+    }
+}
+
+impl ApiRequest for CancelOrderRequest {
+    const BODY: bool = false;
+    const HOST: &'static str = "api-public.sandbox.exchange.coinbase.com";
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const METHOD_OVERRIDE: Option<&'static str> = Some("DELETE");
+    const URI: &'static str = "orders/{order_id}";
+    const PATH_PARAMS: bool = true;
+
+    // id of the cancelled order
+    type Response = String;
+}
+
+#[cfg(test)]
+mod cancel_order_serialize_test {
+    use super::*;
+    use crate::remote_exchanges::PathFormatter;
+
+    #[test]
+    fn test_serialize_cancel_order() {
+        let request = CancelOrderRequest {
+            order_id: "d0c5340b-6d6c-49d9-b567-48c4bfca13d2".to_string(),
+            product_id: Some("BTC-USD".to_string()),
+        };
+
+        assert_eq!(request.to_query_string(), "product_id=BTC-USD");
+        assert_eq!(
+            request.get_path(CancelOrderRequest::URI),
+            "orders/d0c5340b-6d6c-49d9-b567-48c4bfca13d2"
+        );
+    }
+}
+
+#[derive(Deserialize)]
+pub struct GetOrderRequest {
+    pub order_id: String,
+}
+
+impl Serialize for GetOrderRequest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GetOrderRequest", 1)?;
+
+        if is_json_serializer::<S>() {
+            state.serialize_field("order_id", &self.order_id)?;
+        }
+
+        state.end()
+    }
+}
+
+impl ApiRequest for GetOrderRequest {
+    const BODY: bool = false;
+    const HOST: &'static str = "api-public.sandbox.exchange.coinbase.com";
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "orders/{order_id}";
+    const PATH_PARAMS: bool = true;
+
+    type Response = response::Order;
+}
+
 pub struct Statuses(pub Vec<String>);
 
 impl Serialize for Statuses {
@@ -186,6 +273,7 @@ mod test {
             product_id: None,
             market_type: None,
             limit: 10,
+            after: None,
             status: Some(Statuses(vec!["open".to_string(), "pending".to_string()])),
         };
 
@@ -201,6 +289,8 @@ pub struct OrdersRequest {
     pub product_id: Option<String>,
     pub market_type: Option<String>,
     pub limit: u64,
+    /// cursor from cb-after header of the previous page
+    pub after: Option<String>,
     #[serde(flatten)]
     pub status: Option<Statuses>,
 }
@@ -214,6 +304,23 @@ impl ApiRequest for OrdersRequest {
     type Response = Vec<response::Order>;
 }
 
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize)]
+pub struct FillsRequest {
+    pub order_id: Option<String>,
+    pub product_id: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl ApiRequest for FillsRequest {
+    const BODY: bool = false;
+    const HOST: &'static str = "api-public.sandbox.exchange.coinbase.com";
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "fills";
+
+    type Response = Vec<response::Fill>;
+}
+
 #[derive(Deserialize)]
 pub struct GetOrderbookRequest {
     pub product_id: String,
@@ -323,3 +430,65 @@ impl ApiRequest for GetProductCandles {
 
     type Response = Vec<response::CoinbaseCandle>;
 }
+
+#[derive(Deserialize)]
+pub struct GetProductTrades {
+    pub product_id: String,
+    pub limit: Option<u32>,
+    /// trade id cursor, returns trades older than given trade
+    pub after: Option<String>,
+}
+
+impl Serialize for GetProductTrades {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GetProductTrades", 3)?;
+
+        if self.limit.is_some() {
+            state.serialize_field("limit", &self.limit)?;
+        }
+
+        if self.after.is_some() {
+            state.serialize_field("after", &self.after)?;
+        }
+
+        if is_json_serializer::<S>() {
+            state.serialize_field("product_id", &self.product_id)?;
+        }
+
+        state.end()
+    }
+}
+
+impl ApiRequest for GetProductTrades {
+    const BODY: bool = false;
+    const HOST: &'static str = "api.exchange.coinbase.com";
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "products/{product_id}/trades";
+    const PATH_PARAMS: bool = true;
+
+    type Response = Vec<response::Trade>;
+}
+
+#[cfg(test)]
+mod trades_serialize_test {
+    use super::*;
+    use crate::remote_exchanges::PathFormatter;
+
+    #[test]
+    fn test_serialize_trades() {
+        let request = GetProductTrades {
+            product_id: "BTC-USD".to_string(),
+            limit: Some(1000),
+            after: Some("74".to_string()),
+        };
+
+        assert_eq!(request.to_query_string(), "limit=1000&after=74");
+        assert_eq!(
+            request.get_path(GetProductTrades::URI),
+            "products/BTC-USD/trades"
+        );
+    }
+}
@@ -5,15 +5,17 @@ use crate::{
     pair::Pair,
     remote_exchanges::{
         okx::api::InstrumentType,
-        request::OrderSide,
+        request::{OrderSide, OrderType},
         response::{
-            ApiResponseWrapper, Balance, BidAsk as GlobalBidAsk, Instrument,
-            OrderBook as GlobalOrderBook, OrderData,
+            ApiResponseWrapper, Balance, BidAsk as GlobalBidAsk, Fill as GlobalFill, Instrument,
+            Liquidity, Order as GlobalOrder, OrderBook as GlobalOrderBook, OrderData,
+            OrderStatus as GlobalOrderStatus, ReportedOrderType,
         },
         ExchangeErrors,
     },
 };
 use candid::CandidType;
+use chrono::{DateTime, Utc};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 
@@ -232,10 +234,87 @@ pub struct Order {
     #[serde(rename = "type")]
     pub order_type: Option<String>,
     pub time_in_force: Option<String>,
+    pub post_only: Option<bool>,
     #[serde_as(as = "DisplayFromStr")]
     pub filled_size: f64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub executed_value: Option<f64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub fill_fees: Option<f64>,
     #[serde_as(as = "DisplayFromStr")]
     pub status: OrderStatus,
+    /// reason of finishing the order, set when status is done
+    pub done_reason: Option<String>,
+}
+
+impl Order {
+    /// coinbase reports finished orders as done, the outcome is in done reason
+    pub fn status(&self) -> GlobalOrderStatus {
+        match self.status {
+            OrderStatus::Done => match self.done_reason.as_deref() {
+                Some("canceled") => GlobalOrderStatus::Cancelled,
+                Some("rejected") => GlobalOrderStatus::Rejected,
+                Some("filled") => GlobalOrderStatus::Filled,
+                _ => GlobalOrderStatus::Closed,
+            },
+            OrderStatus::Rejected => GlobalOrderStatus::Rejected,
+            // all is only a query filter, orders never report it
+            OrderStatus::Open
+            | OrderStatus::Pending
+            | OrderStatus::Active
+            | OrderStatus::Received
+            | OrderStatus::All => match self.filled_size > 0.0 {
+                true => GlobalOrderStatus::PartiallyFilled,
+                false => GlobalOrderStatus::Open,
+            },
+        }
+    }
+
+    /// time in force and post only flag refine limit orders
+    pub fn order_type(&self) -> ReportedOrderType {
+        let order_type = match (
+            self.order_type.as_deref(),
+            self.time_in_force.as_deref(),
+            self.post_only,
+        ) {
+            (Some("market"), _, _) => OrderType::Market,
+            (Some("limit"), Some("IOC"), _) => OrderType::Ioc,
+            (Some("limit"), Some("FOK"), _) => OrderType::Fok,
+            (Some("limit"), _, Some(true)) => OrderType::PostOnly,
+            (Some("limit"), _, _) => OrderType::Limit,
+            (other, _, _) => {
+                return ReportedOrderType::Other(other.unwrap_or_default().to_string())
+            }
+        };
+
+        ReportedOrderType::Common(order_type)
+    }
+
+    pub fn into_order(self, instrument_type: InstrumentType) -> GlobalOrder {
+        // fees are charged in quote currency
+        let fee_currency = Pair::from_str(&self.product_id).ok().map(|p| p.quote);
+        let state = self.status();
+        let order_type = self.order_type();
+
+        GlobalOrder {
+            instrument_type: instrument_type.to_string(),
+            average_price: self
+                .executed_value
+                .filter(|_| self.filled_size > 0.0)
+                .map(|value| value / self.filled_size),
+            instrument_id: self.product_id,
+            order_id: self.id,
+            price: self.price.unwrap_or(0.0),
+            size: self.size.unwrap_or(self.funds.unwrap_or(0.0)),
+            side: self.side,
+            order_type,
+            trade_mode: self.time_in_force.unwrap_or("".to_string()),
+            accumulated_fill_quantity: self.filled_size,
+            fee: self.fill_fees,
+            fee_currency,
+            state,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize)]
@@ -260,3 +339,147 @@ impl Into<Candle> for CoinbaseCandle {
         }
     }
 }
+
+#[serde_as]
+#[derive(Deserialize, Debug, Clone)]
+pub struct Fill {
+    pub trade_id: u64,
+    pub product_id: String,
+    pub order_id: String,
+    /// M for maker, T for taker
+    pub liquidity: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub size: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub created_at: DateTime<Utc>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub side: OrderSide,
+}
+
+impl From<Fill> for GlobalFill {
+    fn from(value: Fill) -> Self {
+        GlobalFill {
+            // fees are charged in quote currency
+            fee_currency: Pair::from_str(&value.product_id)
+                .map(|p| p.quote)
+                .unwrap_or_default(),
+            instrument_id: value.product_id,
+            order_id: value.order_id,
+            trade_id: value.trade_id.to_string(),
+            side: value.side,
+            price: value.price,
+            size: value.size,
+            fee: value.fee,
+            liquidity: match value.liquidity.as_str() {
+                "T" => Some(Liquidity::Taker),
+                "M" => Some(Liquidity::Maker),
+                _ => None,
+            },
+            timestamp: value.created_at.timestamp() as u64,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Deserialize, Debug, Clone)]
+pub struct Trade {
+    #[serde_as(as = "DisplayFromStr")]
+    pub time: DateTime<Utc>,
+    pub trade_id: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub size: f64,
+    /// side of the maker order, taker traded the opposite side
+    #[serde_as(as = "DisplayFromStr")]
+    pub side: OrderSide,
+}
+
+impl Trade {
+    pub fn timestamp(&self) -> u64 {
+        self.time.timestamp() as u64
+    }
+}
+
+#[cfg(test)]
+mod trade_test {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_trade() {
+        let response = r#"[{"time":"2024-05-01T12:00:30.123456Z","trade_id":74,"price":"10.00000000","size":"0.01000000","side":"sell"}]"#;
+        let trades: Vec<Trade> = serde_json::from_str(response).unwrap();
+
+        assert_eq!(trades[0].timestamp(), 1714564830);
+        assert_eq!(trades[0].trade_id, 74);
+        assert_eq!(trades[0].size, 0.01);
+        assert!(matches!(trades[0].side, OrderSide::Sell));
+    }
+}
+
+#[cfg(test)]
+mod order_test {
+    use super::*;
+
+    #[test]
+    fn test_order_conversion() {
+        let response = r#"{"id":"d0c5340b-6d6c-49d9-b567-48c4bfca13d2","price":"10000.00","size":"0.50","product_id":"BTC-USD","side":"buy","type":"limit","time_in_force":"GTC","filled_size":"0.25","executed_value":"2475.00","fill_fees":"7.425","status":"open"}"#;
+        let order: Order = serde_json::from_str(response).unwrap();
+
+        let order = order.into_order(InstrumentType::Spot);
+
+        assert_eq!(order.accumulated_fill_quantity, 0.25);
+        assert_eq!(order.average_price, Some(9900.0));
+        assert_eq!(order.fee, Some(7.425));
+        assert_eq!(order.fee_currency, Some("USD".to_string()));
+        assert_eq!(order.state, GlobalOrderStatus::PartiallyFilled);
+        assert_eq!(
+            order.order_type,
+            ReportedOrderType::Common(OrderType::Limit)
+        );
+    }
+
+    #[test]
+    fn test_done_order_status() {
+        let response = r#"{"id":"d0c5340b-6d6c-49d9-b567-48c4bfca13d2","price":"10000.00","size":"0.50","product_id":"BTC-USD","side":"sell","type":"limit","time_in_force":"GTC","post_only":true,"filled_size":"0.25","status":"done","done_reason":"canceled"}"#;
+        let order: Order = serde_json::from_str(response).unwrap();
+
+        let order = order.into_order(InstrumentType::Spot);
+
+        assert_eq!(order.state, GlobalOrderStatus::Cancelled);
+        assert_eq!(order.side, OrderSide::Sell);
+        assert_eq!(
+            order.order_type,
+            ReportedOrderType::Common(OrderType::PostOnly)
+        );
+    }
+
+    #[test]
+    fn test_unreported_outcome_is_not_guessed() {
+        let response = r#"{"id":"d0c5340b-6d6c-49d9-b567-48c4bfca13d2","price":"10000.00","size":"0.50","product_id":"BTC-USD","side":"sell","type":"stop","filled_size":"0.25","status":"done"}"#;
+        let order: Order = serde_json::from_str(response).unwrap();
+
+        let order = order.into_order(InstrumentType::Spot);
+
+        assert_eq!(order.state, GlobalOrderStatus::Closed);
+        assert_eq!(
+            order.order_type,
+            ReportedOrderType::Other("stop".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fill_conversion() {
+        let response = r#"[{"trade_id":74,"product_id":"BTC-USD","order_id":"d0c5340b-6d6c-49d9-b567-48c4bfca13d2","user_id":"5cf6e115aaf44503db300f1e","profile_id":"8058d771-2d88-4f0f-ab6e-299c153d4308","liquidity":"T","price":"10000.00","size":"0.01","fee":"0.15","created_at":"2024-05-01T12:00:30.123Z","side":"buy","settled":true,"usd_volume":"100.0"}]"#;
+        let fills: Vec<Fill> = serde_json::from_str(response).unwrap();
+
+        let fill: GlobalFill = fills[0].clone().into();
+
+        assert_eq!(fill.trade_id, "74");
+        assert_eq!(fill.fee_currency, "USD");
+        assert_eq!(fill.liquidity, Some(Liquidity::Taker));
+        assert_eq!(fill.timestamp, 1714564830);
+    }
+}
@@ -1,7 +1,11 @@
 use crate::{
     remote_exchanges::{
-        request::{GeneralInstrumentsRequest, GeneralOrdersListRequest, OrderSide},
-        response::Order,
+        okx::api::InstrumentType,
+        request::{
+            GeneralAmendOrderRequest, GeneralCancelOrderRequest, GeneralFillsRequest,
+            GeneralInstrumentsRequest, GeneralOrderDetailsRequest, GeneralOrdersListRequest,
+        },
+        response::{AmendedOrder, OrderData, OrdersPage},
         ExchangeErrors, UserData,
     },
     request_store::request::Response,
@@ -9,9 +13,12 @@ use crate::{
 
 use super::{
     auth::CoinbaseAuth,
-    request::{GetProfileAccountsRequest, OrdersRequest, PostOrderBody, Statuses},
+    request::{
+        CancelOrderRequest, FillsRequest, GetOrderRequest, GetProfileAccountsRequest,
+        OrdersRequest, PostOrderBody,
+    },
     response::{self, CoinbaseResponse, OrderStatus},
-    Coinbase,
+    Coinbase, CURSOR_HEADER, SIGNATURE_SEPARATOR,
 };
 
 #[async_trait::async_trait]
@@ -44,20 +51,7 @@ impl UserData for Coinbase {
         &self,
         request: crate::remote_exchanges::request::GeneralPostOrderRequest,
     ) -> Result<Response, ExchangeErrors> {
-        let request_coinbase = PostOrderBody {
-            order_type: request.order_type.into(),
-            size: match request.side {
-                OrderSide::Buy => None,
-                OrderSide::Sell => Some(request.size),
-            },
-            side: request.side.into(),
-            funds: match request.side {
-                OrderSide::Buy => Some(request.size),
-                OrderSide::Sell => None,
-            },
-            price: request.order_price,
-            product_id: request.instrument_id.to_string(),
-        };
+        let request_coinbase = Self::post_order_request(&request);
 
         let order = self
             .api_client
@@ -70,91 +64,162 @@ impl UserData for Coinbase {
         Ok(Response::Order(order.into()))
     }
 
-    async fn get_pending_orders(
+    async fn cancel_order(
         &self,
-        request: GeneralOrdersListRequest,
+        request: GeneralCancelOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::cancel_order_request(&request);
+
+        let order_id = self
+            .api_client
+            .call::<CoinbaseResponse<String>, CancelOrderRequest, CoinbaseAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Cancelled(OrderData {
+            id: order_id,
+            message: "".to_string(),
+        }))
+    }
+
+    async fn amend_order(
+        &self,
+        request: GeneralAmendOrderRequest,
     ) -> Result<Response, ExchangeErrors> {
-        let exchange_request = OrdersRequest {
-            market_type: Some(request.instrument_type.to_string()),
-            product_id: Some(request.instrument_id.to_string()),
-            limit: 100,
-            status: Some(Statuses(vec![
-                OrderStatus::Open.to_string(),
-                OrderStatus::Pending.to_string(),
-            ])),
+        let (cancel_request, post_request) = Self::amend_order_requests(&request);
+
+        // cancel and replacement are signed separately
+        let (cancel_auth, post_auth) = match self.auth.as_ref() {
+            Some(auth) => {
+                let (cancel_signature, post_signature) = auth
+                    .signature
+                    .split_once(SIGNATURE_SEPARATOR)
+                    .ok_or(ExchangeErrors::RequestRejected {
+                        message: "amendment requires cancel and order signatures".to_string(),
+                    })?;
+
+                (
+                    Some(auth.with_signature(cancel_signature)),
+                    Some(auth.with_signature(post_signature)),
+                )
+            }
+            None => (None, None),
         };
 
-        let order_response = self
+        self.api_client
+            .call::<CoinbaseResponse<String>, CancelOrderRequest, CoinbaseAuth>(
+                cancel_request,
+                cancel_auth.as_ref(),
+            )
+            .await?;
+
+        let order = self
+            .api_client
+            .call::<CoinbaseResponse<response::OrderResponse>, PostOrderBody, CoinbaseAuth>(
+                post_request,
+                post_auth.as_ref(),
+            )
+            .await
+            .map_err(|e| ExchangeErrors::RequestRejected {
+                message: format!(
+                    "order {} was cancelled but replacement failed: {}",
+                    request.order_id, e
+                ),
+            })?;
+
+        Ok(Response::Amended(AmendedOrder {
+            id: order.id,
+            emulated: true,
+            message: format!("replaced order {}", request.order_id),
+        }))
+    }
+
+    async fn get_order_details(
+        &self,
+        request: GeneralOrderDetailsRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::order_details_request(&request);
+
+        let order = self
             .api_client
-            .call::<CoinbaseResponse<Vec<response::Order>>, OrdersRequest, CoinbaseAuth>(
+            .call::<CoinbaseResponse<response::Order>, GetOrderRequest, CoinbaseAuth>(
                 exchange_request,
                 self.auth.as_ref(),
             )
             .await?;
 
-        Ok(Response::OrdersInfo(
-            order_response
-                .into_iter()
-                .filter_map(|o| match o.status {
-                    OrderStatus::Open | OrderStatus::Pending => Some(Order {
-                        instrument_type: request.instrument_type.to_string(),
-                        instrument_id: o.product_id,
-                        order_id: o.id,
-                        price: o.price.unwrap_or(0.0),
-                        size: o.size.unwrap_or(o.funds.unwrap_or(0.0)),
-                        side: o.side.to_string(),
-                        order_type: o.order_type.unwrap_or("".to_string()),
-                        trade_mode: o.time_in_force.unwrap_or("".to_string()),
-                        accumulated_fill_quantity: o.filled_size,
-                        state: o.status.to_string(),
-                    }),
-                    _ => None,
-                })
-                .collect(),
+        Ok(Response::OrderDetails(
+            order.into_order(InstrumentType::Spot),
         ))
     }
 
+    async fn get_fills(&self, request: GeneralFillsRequest) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::fills_request(&request);
+
+        let fills = self
+            .api_client
+            .call::<CoinbaseResponse<Vec<response::Fill>>, FillsRequest, CoinbaseAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Fills(fills.into_iter().map(Into::into).collect()))
+    }
+
+    async fn get_pending_orders(
+        &self,
+        request: GeneralOrdersListRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        self.get_orders_page(request).await
+    }
+
     async fn get_done_orders(
         &self,
         request: GeneralOrdersListRequest,
     ) -> Result<Response, ExchangeErrors> {
-        let exchange_request = OrdersRequest {
-            market_type: Some(request.instrument_type.to_string()),
-            product_id: Some(request.instrument_id.to_string()),
-            limit: 100,
-            status: Some(Statuses(vec![
-                OrderStatus::Done.to_string(),
-                OrderStatus::Rejected.to_string(),
-            ])),
-        };
+        self.get_orders_page(request).await
+    }
+}
+
+impl Coinbase {
+    /// coinbase returns the cursor of the next page in cb-after header
+    async fn get_orders_page(
+        &self,
+        request: GeneralOrdersListRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::orders_request(&request);
 
-        let order_response = self
+        let (order_response, headers) = self
             .api_client
-            .call::<CoinbaseResponse<Vec<response::Order>>, OrdersRequest, CoinbaseAuth>(
+            .call_with_headers::<CoinbaseResponse<Vec<response::Order>>, OrdersRequest, CoinbaseAuth>(
                 exchange_request,
                 self.auth.as_ref(),
             )
             .await?;
 
-        Ok(Response::OrdersInfo(
-            order_response
+        let next_cursor = match order_response.len() >= request.limit() as usize {
+            true => headers
                 .into_iter()
-                .filter_map(|o| match o.status {
-                    OrderStatus::Done | OrderStatus::Rejected => Some(Order {
-                        instrument_type: request.instrument_type.to_string(),
-                        instrument_id: o.product_id,
-                        order_id: o.id,
-                        price: o.price.unwrap_or(0.0),
-                        size: o.size.unwrap_or(o.funds.unwrap_or(0.0)),
-                        side: o.side.to_string(),
-                        order_type: o.order_type.unwrap_or("".to_string()),
-                        trade_mode: o.time_in_force.unwrap_or("".to_string()),
-                        accumulated_fill_quantity: o.filled_size,
-                        state: o.status.to_string(),
-                    }),
-                    _ => None,
-                })
-                .collect(),
-        ))
+                .find(|h| h.name.eq_ignore_ascii_case(CURSOR_HEADER))
+                .map(|h| h.value),
+            false => None,
+        };
+
+        let orders = order_response
+            .into_iter()
+            .filter(|o| match request.pending {
+                true => matches!(o.status, OrderStatus::Open | OrderStatus::Pending),
+                false => matches!(o.status, OrderStatus::Done | OrderStatus::Rejected),
+            })
+            .map(|o| o.into_order(request.instrument_type))
+            .collect();
+
+        Ok(Response::OrdersInfo(OrdersPage {
+            orders,
+            next_cursor,
+        }))
     }
 }
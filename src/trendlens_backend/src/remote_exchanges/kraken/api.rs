@@ -0,0 +1,234 @@
+use super::response::{
+    AddOrderResult, AmendOrderResult, CancelOrderResult, ClosedOrders, Depth, Ohlc, OpenOrders,
+    OrderInfo, PairInfo, TradesHistory,
+};
+use crate::remote_exchanges::ApiRequest;
+use ic_cdk::api::management_canister::http_request::HttpMethod;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
+use std::collections::HashMap;
+
+const HOST: &str = "api.kraken.com";
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OhlcRequest {
+    pub pair: String,
+    pub interval: Option<u32>,
+    pub since: Option<u64>,
+}
+
+impl ApiRequest for OhlcRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "0/public/OHLC";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = Ohlc;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepthRequest {
+    pub pair: String,
+    pub count: Option<u32>,
+}
+
+impl ApiRequest for DepthRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "0/public/Depth";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = HashMap<String, Depth>;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetPairsRequest {
+    pub pair: Option<String>,
+}
+
+impl ApiRequest for AssetPairsRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "0/public/AssetPairs";
+    const HOST: &'static str = HOST;
+    const BODY: bool = false;
+
+    type Response = HashMap<String, PairInfo>;
+}
+
+// private endpoints are posted as json, nonce has to be the first field
+// because it is also prepended to the body in the signed payload
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceRequest {
+    pub nonce: u64,
+}
+
+impl ApiRequest for BalanceRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "0/private/Balance";
+    const HOST: &'static str = HOST;
+    const BODY: bool = true;
+
+    type Response = HashMap<String, String>;
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AddOrderRequest {
+    pub nonce: u64,
+    #[serde(rename = "ordertype")]
+    pub order_type: String,
+    #[serde(rename = "type")]
+    pub side: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub volume: f64,
+    pub pair: String,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub price: Option<f64>,
+    #[serde(rename = "oflags")]
+    pub order_flags: Option<String>,
+    #[serde(rename = "timeinforce")]
+    pub time_in_force: Option<String>,
+}
+
+impl ApiRequest for AddOrderRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "0/private/AddOrder";
+    const HOST: &'static str = HOST;
+    const BODY: bool = true;
+
+    type Response = AddOrderResult;
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmendOrderRequest {
+    pub nonce: u64,
+    pub txid: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub order_qty: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub limit_price: f64,
+}
+
+impl ApiRequest for AmendOrderRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "0/private/AmendOrder";
+    const HOST: &'static str = HOST;
+    const BODY: bool = true;
+
+    type Response = AmendOrderResult;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelOrderRequest {
+    pub nonce: u64,
+    pub txid: String,
+}
+
+impl ApiRequest for CancelOrderRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "0/private/CancelOrder";
+    const HOST: &'static str = HOST;
+    const BODY: bool = true;
+
+    type Response = CancelOrderResult;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryOrdersRequest {
+    pub nonce: u64,
+    pub txid: String,
+}
+
+impl ApiRequest for QueryOrdersRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "0/private/QueryOrders";
+    const HOST: &'static str = HOST;
+    const BODY: bool = true;
+
+    type Response = HashMap<String, OrderInfo>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenOrdersRequest {
+    pub nonce: u64,
+}
+
+impl ApiRequest for OpenOrdersRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "0/private/OpenOrders";
+    const HOST: &'static str = HOST;
+    const BODY: bool = true;
+
+    type Response = OpenOrders;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosedOrdersRequest {
+    pub nonce: u64,
+}
+
+impl ApiRequest for ClosedOrdersRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "0/private/ClosedOrders";
+    const HOST: &'static str = HOST;
+    const BODY: bool = true;
+
+    type Response = ClosedOrders;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradesHistoryRequest {
+    pub nonce: u64,
+}
+
+impl ApiRequest for TradesHistoryRequest {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "0/private/TradesHistory";
+    const HOST: &'static str = HOST;
+    const BODY: bool = true;
+
+    type Response = TradesHistory;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ohlc_query_string() {
+        let request = OhlcRequest {
+            pair: "XBTUSD".to_string(),
+            interval: Some(1),
+            since: Some(1688671200),
+        };
+
+        assert_eq!(
+            request.to_query_string(),
+            "pair=XBTUSD&interval=1&since=1688671200"
+        );
+    }
+
+    #[test]
+    fn test_add_order_body() {
+        let request = AddOrderRequest {
+            nonce: 1616492376594,
+            order_type: "limit".to_string(),
+            side: "buy".to_string(),
+            volume: 1.25,
+            pair: "XBTUSD".to_string(),
+            price: Some(27500.0),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            request.to_body(),
+            r#"{"nonce":1616492376594,"ordertype":"limit","type":"buy","volume":"1.25","pair":"XBTUSD","price":"27500"}"#
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use super::Kraken;
+use crate::remote_exchanges::Authorize;
+use ic_cdk::api::management_canister::http_request::HttpHeader;
+
+pub struct KrakenAuth {
+    pub api_key: String,
+    pub signature: String,
+    /// nonce embedded in the signed postdata
+    pub nonce: u64,
+}
+
+impl Authorize for KrakenAuth {
+    fn get_auth_headers(&self) -> Vec<HttpHeader> {
+        vec![
+            HttpHeader {
+                name: "API-Key".to_string(),
+                value: self.api_key.clone(),
+            },
+            HttpHeader {
+                name: "API-Sign".to_string(),
+                value: self.signature.clone(),
+            },
+        ]
+    }
+}
+
+impl Kraken {
+    pub fn with_auth(auth: KrakenAuth) -> Self {
+        Self {
+            auth: Some(auth),
+            ..Default::default()
+        }
+    }
+}
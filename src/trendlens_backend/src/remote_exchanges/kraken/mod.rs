@@ -0,0 +1,308 @@
+use super::request::{
+    GeneralAmendOrderRequest, GeneralCancelOrderRequest, GeneralOrderDetailsRequest,
+    GeneralPostOrderRequest, OrderSide, OrderType,
+};
+use super::{ApiRequest, ExchangeErrors};
+use crate::{
+    api_client::ApiClient,
+    chain_data::ChainData,
+    exchange::{Exchange, ExchangeId},
+    interval::Interval,
+    pair::Pair,
+};
+use api::{AddOrderRequest, AmendOrderRequest, CancelOrderRequest};
+pub use api::{
+    BalanceRequest, ClosedOrdersRequest, OpenOrdersRequest, QueryOrdersRequest,
+    TradesHistoryRequest,
+};
+pub use auth::KrakenAuth;
+use std::str::FromStr;
+
+pub mod api;
+pub mod auth;
+pub mod open;
+pub mod response;
+pub mod user;
+
+const PRIVATE_PATH_PREFIX: &str = "/0/private/";
+
+/// stands for the nonce in stored signature data, kraken rejects nonces it has
+/// already seen or lower than the last one, so it is only known when a leg is signed
+pub const NONCE_PLACEHOLDER: &str = "{nonce}";
+
+/// nonce the requests are built with for signature data, replaced by the placeholder
+pub const TEMPLATE_NONCE: u64 = 0;
+
+pub struct Kraken {
+    auth: Option<KrakenAuth>,
+    api_client: ApiClient,
+}
+
+impl Default for Kraken {
+    fn default() -> Self {
+        Self {
+            auth: None,
+            api_client: ApiClient::new(Exchange::Kraken),
+        }
+    }
+}
+
+impl Kraken {
+    /// kraken still uses XBT as bitcoin asset code
+    pub fn instrument_id(pair: &Pair) -> String {
+        let asset = |a: &str| match a.to_uppercase().as_str() {
+            "BTC" => "XBT".to_string(),
+            other => other.to_string(),
+        };
+
+        format!("{}{}", asset(&pair.base), asset(&pair.quote))
+    }
+
+    /// converts websocket pair name (XBT/USD) into common pair
+    pub fn pair_from_wsname(wsname: &str) -> Option<Pair> {
+        let pair = Pair::from_str(wsname).ok()?;
+        let asset = |a: String| match a.as_str() {
+            "XBT" => "BTC".to_string(),
+            _ => a,
+        };
+
+        Some(Pair {
+            base: asset(pair.base),
+            quote: asset(pair.quote),
+        })
+    }
+
+    /// gets interval in minutes
+    pub fn interval_minutes(interval: Interval) -> u32 {
+        (interval.seconds() / 60) as u32
+    }
+
+    pub fn side_string(side: OrderSide) -> String {
+        side.to_string()
+    }
+
+    /// maps general order type into kraken order type, order flags and time in force
+    pub fn order_type_string(
+        order_type: OrderType,
+    ) -> Result<(String, Option<String>, Option<String>), ExchangeErrors> {
+        let (order_type, flags, time_in_force) = match order_type {
+            OrderType::Market => ("market", None, None),
+            OrderType::Limit => ("limit", None, None),
+            OrderType::PostOnly => ("limit", Some("post"), None),
+            OrderType::Ioc => ("limit", None, Some("IOC")),
+            // kraken spot does not support fill or kill
+            OrderType::Fok => {
+                return Err(ExchangeErrors::RequestRejected {
+                    message: "kraken does not support fill or kill orders".to_string(),
+                })
+            }
+        };
+
+        Ok((
+            order_type.to_string(),
+            flags.map(|f| f.to_string()),
+            time_in_force.map(|t| t.to_string()),
+        ))
+    }
+
+    pub fn add_order_request(
+        nonce: u64,
+        request: &GeneralPostOrderRequest,
+    ) -> Result<AddOrderRequest, ExchangeErrors> {
+        let (order_type, order_flags, time_in_force) = Self::order_type_string(request.order_type)?;
+
+        Ok(AddOrderRequest {
+            nonce,
+            order_type,
+            side: Self::side_string(request.side),
+            volume: request.size,
+            pair: Self::instrument_id(&request.instrument_id),
+            price: request.order_price,
+            order_flags,
+            time_in_force,
+        })
+    }
+
+    /// amends order in place, the order keeps its txid
+    pub fn amend_order_request(
+        nonce: u64,
+        request: &GeneralAmendOrderRequest,
+    ) -> AmendOrderRequest {
+        AmendOrderRequest {
+            nonce,
+            txid: request.order_id.clone(),
+            order_qty: request.size,
+            limit_price: request.price,
+        }
+    }
+
+    pub fn cancel_order_request(
+        nonce: u64,
+        request: &GeneralCancelOrderRequest,
+    ) -> CancelOrderRequest {
+        CancelOrderRequest {
+            nonce,
+            txid: request.order_id.clone(),
+        }
+    }
+
+    pub fn balance_request(nonce: u64) -> BalanceRequest {
+        BalanceRequest { nonce }
+    }
+
+    pub fn order_details_request(
+        nonce: u64,
+        request: &GeneralOrderDetailsRequest,
+    ) -> QueryOrdersRequest {
+        QueryOrdersRequest {
+            nonce,
+            txid: request.order_id.clone(),
+        }
+    }
+
+    /// trades history is not filtered by kraken, fills are picked from the response
+    pub fn fills_request(nonce: u64) -> TradesHistoryRequest {
+        TradesHistoryRequest { nonce }
+    }
+
+    /// open and closed orders are not filtered by pair, orders are picked from the response
+    pub fn open_orders_request(nonce: u64) -> OpenOrdersRequest {
+        OpenOrdersRequest { nonce }
+    }
+
+    pub fn closed_orders_request(nonce: u64) -> ClosedOrdersRequest {
+        ClosedOrdersRequest { nonce }
+    }
+
+    /// nonce of a leg signed at `timestamp_millis`, later signatures get larger
+    /// nonces and legs of one transaction differ by their position
+    pub fn execution_nonce(timestamp_millis: u64, position: usize) -> u64 {
+        timestamp_millis * 1000 + position as u64
+    }
+
+    /// returns `path + nonce + postdata` with [`NONCE_PLACEHOLDER`] for the nonce,
+    /// the request is built with [`TEMPLATE_NONCE`] as its first postdata field;
+    /// kraken signs `path + sha256(nonce + postdata)` with hmac-sha512, so signers
+    /// fill in the nonce with [`Kraken::with_nonce`] and split it with
+    /// [`Kraken::split_signature_data`]
+    pub fn get_signature_data<R: ApiRequest>(&self, request: R) -> String {
+        let postdata = request.to_body().replacen(
+            &format!(r#"{{"nonce":{}"#, TEMPLATE_NONCE),
+            &format!(r#"{{"nonce":{}"#, NONCE_PLACEHOLDER),
+            1,
+        );
+
+        format!("/{}{}{}", R::URI, NONCE_PLACEHOLDER, postdata)
+    }
+
+    pub fn with_nonce(signature_data: &str, nonce: u64) -> String {
+        signature_data.replace(NONCE_PLACEHOLDER, &nonce.to_string())
+    }
+
+    /// splits signature data into the path and `nonce + postdata`, private
+    /// endpoint names consist of letters only
+    pub fn split_signature_data(signature_data: &str) -> Option<(&str, &str)> {
+        let name = signature_data.strip_prefix(PRIVATE_PATH_PREFIX)?;
+        let name_len = name
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(name.len());
+
+        Some(signature_data.split_at(PRIVATE_PATH_PREFIX.len() + name_len))
+    }
+
+    fn nonce(&self) -> u64 {
+        self.auth.as_ref().map(|a| a.nonce).unwrap_or_default()
+    }
+}
+
+impl ExchangeId for Kraken {
+    fn exchange_id(&self) -> Exchange {
+        Exchange::Kraken
+    }
+}
+
+impl ChainData for Kraken {
+    fn key(&self) -> Exchange {
+        Exchange::Kraken
+    }
+}
+
+#[cfg(test)]
+mod test_kraken_helpers {
+    use super::*;
+    use crate::remote_exchanges::request::TradeMode;
+
+    #[test]
+    fn test_kraken_instrument_id() {
+        let pair = Pair {
+            base: "btc".to_string(),
+            quote: "usd".to_string(),
+        };
+
+        assert_eq!(Kraken::instrument_id(&pair), "XBTUSD");
+        assert_eq!(
+            Kraken::pair_from_wsname("XBT/USD").unwrap(),
+            Pair {
+                base: "BTC".to_string(),
+                quote: "USD".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_signature_data() {
+        let kraken = Kraken::default();
+
+        let request = GeneralPostOrderRequest {
+            instrument_id: Pair {
+                base: "BTC".to_string(),
+                quote: "USD".to_string(),
+            },
+            trade_mode: TradeMode::Cash,
+            side: OrderSide::Buy,
+            margin_currency: None,
+            position_side: None,
+            order_type: OrderType::Limit,
+            size: 1.25,
+            order_price: Some(37500.0),
+        };
+
+        let signature_data =
+            kraken.get_signature_data(Kraken::add_order_request(TEMPLATE_NONCE, &request).unwrap());
+
+        assert_eq!(
+            signature_data,
+            r#"/0/private/AddOrder{nonce}{"nonce":{nonce},"ordertype":"limit","type":"buy","volume":"1.25","pair":"XBTUSD","price":"37500"}"#
+        );
+
+        let signature_data = Kraken::with_nonce(&signature_data, 1616492376594);
+        assert_eq!(
+            signature_data,
+            r#"/0/private/AddOrder1616492376594{"nonce":1616492376594,"ordertype":"limit","type":"buy","volume":"1.25","pair":"XBTUSD","price":"37500"}"#
+        );
+        assert_eq!(
+            Kraken::split_signature_data(&signature_data).unwrap().0,
+            "/0/private/AddOrder"
+        );
+    }
+
+    #[test]
+    fn test_fok_order_is_rejected() {
+        assert!(matches!(
+            Kraken::order_type_string(OrderType::Fok),
+            Err(ExchangeErrors::RequestRejected { .. })
+        ));
+    }
+
+    #[test]
+    fn test_execution_nonce_grows_with_signing_time() {
+        let retried = Kraken::execution_nonce(1_717_200_000_001, 0);
+
+        assert!(retried > Kraken::execution_nonce(1_717_200_000_000, 0));
+        assert!(retried > Kraken::execution_nonce(1_717_200_000_000, 999));
+        assert!(
+            Kraken::execution_nonce(1_717_200_000_000, 1)
+                > Kraken::execution_nonce(1_717_200_000_000, 0)
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use super::api::{AssetPairsRequest, DepthRequest, OhlcRequest};
+use super::auth::KrakenAuth;
+use super::response::{Depth, KrakenResponse, Ohlc, PairInfo};
+use super::Kraken;
+use crate::exchange::TimeVolume;
+use crate::interval::Interval;
+use crate::remote_exchanges::request::GeneralInstrumentsRequest;
+use crate::remote_exchanges::response::{Instrument, OrderBook as GlobalOrderBook};
+use crate::{
+    exchange::Candle,
+    pair::Pair,
+    remote_exchanges::{ExchangeErrors, OpenData},
+};
+use std::collections::HashMap;
+
+#[async_trait::async_trait]
+impl OpenData for Kraken {
+    async fn fetch_candles(
+        &self,
+        pair: &Pair,
+        range: std::ops::Range<u64>,
+        interval: Interval,
+    ) -> Result<Vec<Candle>, ExchangeErrors> {
+        let mut current = range.start;
+        let mut candles = vec![];
+
+        log!("Kraken: fetching range {:?}", range);
+
+        // kraken serves at most 720 most recent entries, older data is not available
+        while current < range.end {
+            let request = OhlcRequest {
+                pair: Kraken::instrument_id(pair),
+                interval: Some(Kraken::interval_minutes(interval)),
+                since: Some(current),
+            };
+
+            let response = self
+                .api_client
+                .call::<KrakenResponse<Ohlc>, OhlcRequest, KrakenAuth>(request, None)
+                .await?;
+
+            let batch = response
+                .pairs
+                .into_values()
+                .flatten()
+                .map(Into::<Candle>::into)
+                .filter(|c| c.timestamp >= current && c.timestamp < range.end)
+                .collect::<Vec<_>>();
+
+            if batch.is_empty() || response.last <= current {
+                break;
+            }
+
+            candles.extend(batch);
+            current = response.last;
+        }
+
+        Ok(candles)
+    }
+
+    async fn get_taker_volume(
+        &self,
+        pair: &Pair,
+        range: std::ops::Range<u64>,
+    ) -> Result<Vec<TimeVolume>, ExchangeErrors> {
+        self.fetch_candles(pair, range, Interval::FiveMinutes)
+            .await
+            .map(|candles| {
+                candles
+                    .into_iter()
+                    .map(|candle| TimeVolume::new(candle.timestamp, candle.volume))
+                    .collect()
+            })
+    }
+
+    async fn get_public_instruments(
+        &self,
+        _request: GeneralInstrumentsRequest,
+    ) -> Result<Vec<Instrument>, ExchangeErrors> {
+        let request = AssetPairsRequest { pair: None };
+
+        let response = self
+            .api_client
+            .call::<KrakenResponse<HashMap<String, PairInfo>>, AssetPairsRequest, KrakenAuth>(
+                request, None,
+            )
+            .await?;
+
+        Ok(response
+            .into_values()
+            .filter(|p| p.status.as_deref().unwrap_or("online") == "online")
+            .filter_map(|p| p.into_instrument())
+            .collect())
+    }
+
+    async fn get_orderbook(
+        &self,
+        pair: &Pair,
+        size: u32,
+    ) -> Result<GlobalOrderBook, ExchangeErrors> {
+        let request = DepthRequest {
+            pair: Kraken::instrument_id(pair),
+            count: Some(size),
+        };
+
+        let response = self
+            .api_client
+            .call::<KrakenResponse<HashMap<String, Depth>>, DepthRequest, KrakenAuth>(request, None)
+            .await?;
+
+        Ok(response
+            .into_values()
+            .next()
+            .ok_or(ExchangeErrors::MissingOrderbook)?
+            .into())
+    }
+}
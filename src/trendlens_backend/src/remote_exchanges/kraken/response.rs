@@ -0,0 +1,372 @@
+use super::Kraken;
+use crate::{
+    exchange::Candle,
+    remote_exchanges::{
+        okx::api::InstrumentType,
+        request::{OrderSide, OrderType},
+        response::{
+            ApiResponseWrapper, BidAsk as GlobalBidAsk, Fill, Instrument, Liquidity,
+            Order as GlobalOrder, OrderBook as GlobalOrderBook, OrderData,
+            OrderStatus as GlobalOrderStatus, ReportedOrderType,
+        },
+        ExchangeErrors,
+    },
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct KrakenResponse<T> {
+    pub error: Vec<String>,
+    pub result: Option<T>,
+}
+
+impl<R: DeserializeOwned> ApiResponseWrapper<R> for KrakenResponse<R> {
+    fn extract_response(self) -> Result<R, ExchangeErrors> {
+        // kraken reports failures with http 200 and non empty error list
+        if !self.error.is_empty() {
+            return Err(ExchangeErrors::RequestRejected {
+                message: self.error.join(", "),
+            });
+        }
+
+        self.result.ok_or(ExchangeErrors::RequestRejected {
+            message: "empty result".to_string(),
+        })
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct OhlcEntry(
+    pub u64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    pub u64,
+);
+
+impl From<OhlcEntry> for Candle {
+    fn from(value: OhlcEntry) -> Self {
+        Candle {
+            timestamp: value.0,
+            open_price: value.1,
+            highest_price: value.2,
+            lowest_price: value.3,
+            close_price: value.4,
+            volume: value.6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Ohlc {
+    pub last: u64,
+    // keyed by kraken internal pair name, e.g. XXBTZUSD
+    #[serde(flatten)]
+    pub pairs: HashMap<String, Vec<OhlcEntry>>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct BidAsk(
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    #[serde_as(as = "DisplayFromStr")] pub f64,
+    pub u64,
+);
+
+impl From<BidAsk> for GlobalBidAsk {
+    fn from(value: BidAsk) -> Self {
+        GlobalBidAsk {
+            price: value.0,
+            size: value.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Depth {
+    pub asks: Vec<BidAsk>,
+    pub bids: Vec<BidAsk>,
+}
+
+impl From<Depth> for GlobalOrderBook {
+    fn from(value: Depth) -> Self {
+        GlobalOrderBook {
+            sequence: value
+                .asks
+                .iter()
+                .chain(value.bids.iter())
+                .map(|e| e.2)
+                .max()
+                .unwrap_or_default(),
+            asks: value.asks.into_iter().map(|x| x.into()).collect(),
+            bids: value.bids.into_iter().map(|x| x.into()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PairInfo {
+    pub wsname: Option<String>,
+    pub status: Option<String>,
+}
+
+impl PairInfo {
+    pub fn into_instrument(self) -> Option<Instrument> {
+        Some(Instrument {
+            instrument_id: Kraken::pair_from_wsname(&self.wsname?)?,
+            instrument_type: InstrumentType::Spot,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderDescription {
+    pub order: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddOrderResult {
+    pub descr: OrderDescription,
+    pub txid: Vec<String>,
+}
+
+impl From<AddOrderResult> for OrderData {
+    fn from(value: AddOrderResult) -> Self {
+        OrderData {
+            id: value.txid.into_iter().next().unwrap_or_default(),
+            message: value.descr.order,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AmendOrderResult {
+    pub amend_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CancelOrderResult {
+    pub count: u32,
+    pub pending: Option<bool>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderInfoDescription {
+    pub pair: String,
+    #[serde(rename = "type")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub side: OrderSide,
+    #[serde(rename = "ordertype")]
+    pub order_type: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatus {
+    Pending,
+    Open,
+    Closed,
+    Canceled,
+    Expired,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderInfo {
+    pub status: OrderStatus,
+    pub descr: OrderInfoDescription,
+    #[serde_as(as = "DisplayFromStr")]
+    pub vol: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub vol_exec: f64,
+    /// average price of executed volume
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee: f64,
+    /// comma separated order flags
+    #[serde(default)]
+    pub oflags: String,
+}
+
+impl OrderInfo {
+    /// closed orders on kraken are fully executed ones
+    pub fn status(&self) -> GlobalOrderStatus {
+        match self.status {
+            OrderStatus::Pending | OrderStatus::Open => match self.vol_exec > 0.0 {
+                true => GlobalOrderStatus::PartiallyFilled,
+                false => GlobalOrderStatus::Open,
+            },
+            OrderStatus::Closed => GlobalOrderStatus::Filled,
+            OrderStatus::Canceled => GlobalOrderStatus::Cancelled,
+            OrderStatus::Expired => GlobalOrderStatus::Expired,
+        }
+    }
+
+    pub fn order_type(&self) -> ReportedOrderType {
+        let order_type = match self.descr.order_type.as_str() {
+            "market" => OrderType::Market,
+            "limit" if self.oflags.split(',').any(|f| f == "post") => OrderType::PostOnly,
+            "limit" => OrderType::Limit,
+            other => return ReportedOrderType::Other(other.to_string()),
+        };
+
+        ReportedOrderType::Common(order_type)
+    }
+
+    pub fn into_order(self, order_id: String) -> GlobalOrder {
+        GlobalOrder {
+            instrument_type: InstrumentType::Spot.to_string(),
+            state: self.status(),
+            order_type: self.order_type(),
+            instrument_id: self.descr.pair,
+            order_id,
+            price: self.descr.price,
+            size: self.vol,
+            side: self.descr.side,
+            trade_mode: "cash".to_string(),
+            accumulated_fill_quantity: self.vol_exec,
+            average_price: (self.vol_exec > 0.0).then_some(self.price),
+            fee: Some(self.fee),
+            // depends on order flags, quote currency by default
+            fee_currency: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenOrders {
+    pub open: HashMap<String, OrderInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClosedOrders {
+    pub closed: HashMap<String, OrderInfo>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradeInfo {
+    pub ordertxid: String,
+    pub pair: String,
+    pub time: f64,
+    #[serde(rename = "type")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub side: OrderSide,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub vol: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee: f64,
+    pub maker: Option<bool>,
+}
+
+impl TradeInfo {
+    /// kraken does not report fee currency per trade, fees are taken in quote by default
+    pub fn into_fill(self, trade_id: String, fee_currency: String) -> Fill {
+        Fill {
+            instrument_id: self.pair,
+            order_id: self.ordertxid,
+            trade_id,
+            side: self.side,
+            price: self.price,
+            size: self.vol,
+            fee: self.fee,
+            fee_currency,
+            liquidity: self.maker.map(|maker| match maker {
+                true => Liquidity::Maker,
+                false => Liquidity::Taker,
+            }),
+            timestamp: self.time as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradesHistory {
+    pub trades: HashMap<String, TradeInfo>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_ohlc() {
+        let response = r#"{"error":[],"result":{"XXBTZUSD":[[1688671200,"30306.1","30306.2","30305.7","30305.7","30306.1","3.39243896",23]],"last":1688672160}}"#;
+        let response: KrakenResponse<Ohlc> = serde_json::from_str(response).unwrap();
+        let ohlc = response.extract_response().unwrap();
+
+        assert_eq!(ohlc.last, 1688672160);
+
+        let candle: Candle = ohlc.pairs["XXBTZUSD"][0].clone().into();
+
+        assert_eq!(candle.timestamp, 1688671200);
+        assert_eq!(candle.open_price, 30306.1);
+        assert_eq!(candle.close_price, 30305.7);
+        assert_eq!(candle.volume, 3.39243896);
+    }
+
+    #[test]
+    fn test_extract_error() {
+        let response = r#"{"error":["EGeneral:Invalid arguments"]}"#;
+        let response: KrakenResponse<Ohlc> = serde_json::from_str(response).unwrap();
+
+        assert!(matches!(
+            response.extract_response(),
+            Err(ExchangeErrors::RequestRejected { message }) if message == "EGeneral:Invalid arguments"
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_open_orders() {
+        let response = r#"{"error":[],"result":{"open":{"OQCLML-BW3P3-BUCMWZ":{"refid":null,"userref":0,"status":"open","opentm":1688666559.8974,"starttm":0,"expiretm":0,"descr":{"pair":"XBTUSD","type":"buy","ordertype":"limit","price":"30010.0","price2":"0","leverage":"none","order":"buy 1.25000000 XBTUSD @ limit 30010.0","close":""},"vol":"1.25000000","vol_exec":"0.37500000","cost":"11253.7","fee":"0.00000","price":"30010.0","stopprice":"0.00000","limitprice":"0.00000","misc":"","oflags":"fciq"}}}}"#;
+        let response: KrakenResponse<OpenOrders> = serde_json::from_str(response).unwrap();
+        let (id, info) = response
+            .extract_response()
+            .unwrap()
+            .open
+            .into_iter()
+            .next()
+            .unwrap();
+
+        let order = info.into_order(id);
+
+        assert_eq!(order.order_id, "OQCLML-BW3P3-BUCMWZ");
+        assert_eq!(order.instrument_id, "XBTUSD");
+        assert_eq!(order.price, 30010.0);
+        assert_eq!(order.accumulated_fill_quantity, 0.375);
+        assert_eq!(order.state, GlobalOrderStatus::PartiallyFilled);
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(
+            order.order_type,
+            ReportedOrderType::Common(OrderType::Limit)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_trades_history() {
+        let response = r#"{"error":[],"result":{"trades":{"THVRQM-33VKH-UCI7BS":{"ordertxid":"OQCLML-BW3P3-BUCMWZ","postxid":"TKH2SE-M7IF5-CFI7LT","pair":"XXBTZUSD","time":1688667796.8802,"type":"buy","ordertype":"limit","price":"30010.00000","cost":"600.20000","fee":"0.00000","vol":"0.02000000","margin":"0.00000","misc":"","maker":true}},"count":1}}"#;
+        let response: KrakenResponse<TradesHistory> = serde_json::from_str(response).unwrap();
+        let history = response.extract_response().unwrap();
+
+        let (id, trade) = history.trades.into_iter().next().unwrap();
+        let fill = trade.into_fill(id, "USD".to_string());
+
+        assert_eq!(fill.trade_id, "THVRQM-33VKH-UCI7BS");
+        assert_eq!(fill.order_id, "OQCLML-BW3P3-BUCMWZ");
+        assert_eq!(fill.size, 0.02);
+        assert_eq!(fill.liquidity, Some(Liquidity::Maker));
+        assert_eq!(fill.timestamp, 1688667796);
+    }
+}
@@ -0,0 +1,231 @@
+use super::{
+    api::{
+        AddOrderRequest, AmendOrderRequest, BalanceRequest, CancelOrderRequest,
+        ClosedOrdersRequest, OpenOrdersRequest, QueryOrdersRequest, TradesHistoryRequest,
+    },
+    auth::KrakenAuth,
+    response::{
+        AddOrderResult, AmendOrderResult, CancelOrderResult, ClosedOrders, KrakenResponse,
+        OpenOrders, OrderInfo, TradesHistory,
+    },
+    Kraken,
+};
+use crate::{
+    remote_exchanges::{
+        request::{
+            GeneralAmendOrderRequest, GeneralBalanceRequest, GeneralCancelOrderRequest,
+            GeneralFillsRequest, GeneralInstrumentsRequest, GeneralOrderDetailsRequest,
+            GeneralOrdersListRequest, GeneralPostOrderRequest,
+        },
+        response::{AmendedOrder, Balance, OrderData, OrdersPage},
+        ExchangeErrors, OpenData, UserData,
+    },
+    request_store::request::Response,
+};
+use std::collections::HashMap;
+
+#[async_trait::async_trait]
+impl UserData for Kraken {
+    async fn get_instruments(
+        &self,
+        req: GeneralInstrumentsRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        Ok(Response::Instruments(
+            self.get_public_instruments(req).await?,
+        ))
+    }
+
+    async fn get_balance(
+        &self,
+        request: GeneralBalanceRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::balance_request(self.nonce());
+
+        let balances = self
+            .api_client
+            .call::<KrakenResponse<HashMap<String, String>>, BalanceRequest, KrakenAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Balances(
+            balances
+                .into_iter()
+                .filter(|(currency, _)| match request.currency {
+                    Some(ref currencies) => currencies.contains(currency),
+                    None => true,
+                })
+                .map(|(currency, balance)| Balance {
+                    currency,
+                    available: balance.clone(),
+                    balance,
+                    hold: "0".to_string(),
+                })
+                .collect(),
+        ))
+    }
+
+    async fn post_order(
+        &self,
+        request: GeneralPostOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::add_order_request(self.nonce(), &request)?;
+
+        let order_response = self
+            .api_client
+            .call::<KrakenResponse<AddOrderResult>, AddOrderRequest, KrakenAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Order(order_response.into()))
+    }
+
+    async fn cancel_order(
+        &self,
+        request: GeneralCancelOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::cancel_order_request(self.nonce(), &request);
+
+        let cancel_response = self
+            .api_client
+            .call::<KrakenResponse<CancelOrderResult>, CancelOrderRequest, KrakenAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Cancelled(OrderData {
+            id: request.order_id,
+            message: match cancel_response.pending {
+                Some(true) => "cancellation pending".to_string(),
+                _ => format!("{} orders cancelled", cancel_response.count),
+            },
+        }))
+    }
+
+    async fn amend_order(
+        &self,
+        request: GeneralAmendOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::amend_order_request(self.nonce(), &request);
+
+        let amend_response = self
+            .api_client
+            .call::<KrakenResponse<AmendOrderResult>, AmendOrderRequest, KrakenAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Amended(AmendedOrder {
+            id: request.order_id,
+            emulated: false,
+            message: amend_response.amend_id,
+        }))
+    }
+
+    async fn get_order_details(
+        &self,
+        request: GeneralOrderDetailsRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::order_details_request(self.nonce(), &request);
+
+        let order_response = self
+            .api_client
+            .call::<KrakenResponse<HashMap<String, OrderInfo>>, QueryOrdersRequest, KrakenAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        let (id, order) = order_response
+            .into_iter()
+            .next()
+            .ok_or(ExchangeErrors::MissingOrder)?;
+
+        Ok(Response::OrderDetails(order.into_order(id)))
+    }
+
+    async fn get_fills(&self, request: GeneralFillsRequest) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::fills_request(self.nonce());
+
+        let history = self
+            .api_client
+            .call::<KrakenResponse<TradesHistory>, TradesHistoryRequest, KrakenAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        let instrument_id = Self::instrument_id(&request.instrument_id);
+
+        Ok(Response::Fills(
+            history
+                .trades
+                .into_iter()
+                .filter(|(_, t)| match &request.order_id {
+                    Some(order_id) => &t.ordertxid == order_id,
+                    None => t.pair == instrument_id,
+                })
+                .map(|(id, t)| t.into_fill(id, request.instrument_id.quote.clone()))
+                .collect(),
+        ))
+    }
+
+    async fn get_pending_orders(
+        &self,
+        request: GeneralOrdersListRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::open_orders_request(self.nonce());
+
+        let order_response = self
+            .api_client
+            .call::<KrakenResponse<OpenOrders>, OpenOrdersRequest, KrakenAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        let instrument_id = Self::instrument_id(&request.instrument_id);
+
+        Ok(Response::OrdersInfo(OrdersPage {
+            orders: order_response
+                .open
+                .into_iter()
+                .filter(|(_, o)| o.descr.pair == instrument_id)
+                .map(|(id, o)| o.into_order(id))
+                .collect(),
+            next_cursor: None,
+        }))
+    }
+
+    async fn get_done_orders(
+        &self,
+        request: GeneralOrdersListRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::closed_orders_request(self.nonce());
+
+        let order_response = self
+            .api_client
+            .call::<KrakenResponse<ClosedOrders>, ClosedOrdersRequest, KrakenAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        let instrument_id = Self::instrument_id(&request.instrument_id);
+
+        Ok(Response::OrdersInfo(OrdersPage {
+            orders: order_response
+                .closed
+                .into_iter()
+                .filter(|(_, o)| o.descr.pair == instrument_id)
+                .map(|(id, o)| o.into_order(id))
+                .collect(),
+            next_cursor: None,
+        }))
+    }
+}
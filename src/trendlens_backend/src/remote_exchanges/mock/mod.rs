@@ -0,0 +1,54 @@
+use super::ExchangeErrors;
+use crate::{
+    chain_data::ChainData,
+    exchange::{Exchange, ExchangeId},
+};
+use serde::de::DeserializeOwned;
+use std::cell::RefCell;
+
+pub mod open;
+pub mod user;
+
+const CANDLES: &str = include_str!("fixtures/candles.json");
+const ORDERBOOK: &str = include_str!("fixtures/orderbook.json");
+const INSTRUMENTS: &str = include_str!("fixtures/instruments.json");
+const BALANCES: &str = include_str!("fixtures/balances.json");
+const ORDERS: &str = include_str!("fixtures/orders.json");
+const FILLS: &str = include_str!("fixtures/fills.json");
+
+thread_local! {
+    static LAST_ORDER_ID: RefCell<u64> = const { RefCell::new(0) };
+}
+
+/// exchange serving canned responses from fixtures, it does no outcalls
+/// so the canister flows can run under cargo test
+#[derive(Default)]
+pub struct Mock;
+
+impl Mock {
+    fn fixture<T: DeserializeOwned>(data: &str) -> Result<T, ExchangeErrors> {
+        serde_json::from_str(data).map_err(|e| ExchangeErrors::DeserializationFailed {
+            message: e.to_string(),
+        })
+    }
+
+    /// order ids are sequential, so repeated runs produce the same ids
+    fn next_order_id() -> String {
+        LAST_ORDER_ID.with_borrow_mut(|last| {
+            *last += 1;
+            format!("mock-{}", last)
+        })
+    }
+}
+
+impl ExchangeId for Mock {
+    fn exchange_id(&self) -> Exchange {
+        Exchange::Mock
+    }
+}
+
+impl ChainData for Mock {
+    fn key(&self) -> Exchange {
+        Exchange::Mock
+    }
+}
@@ -0,0 +1,69 @@
+use super::{Mock, CANDLES, INSTRUMENTS, ORDERBOOK};
+use crate::{
+    exchange::{Candle, TimeVolume},
+    interval::Interval,
+    pair::Pair,
+    remote_exchanges::{
+        request::GeneralInstrumentsRequest,
+        response::{Instrument, OrderBook},
+        ExchangeErrors, OpenData,
+    },
+};
+
+#[async_trait::async_trait]
+impl OpenData for Mock {
+    async fn get_public_instruments(
+        &self,
+        request: GeneralInstrumentsRequest,
+    ) -> Result<Vec<Instrument>, ExchangeErrors> {
+        let instruments: Vec<Instrument> = Self::fixture(INSTRUMENTS)?;
+
+        Ok(instruments
+            .into_iter()
+            .filter(|i| i.instrument_type == request.instrument_type)
+            .filter(|i| match request.instrument_id {
+                Some(ref pair) => &i.instrument_id == pair,
+                None => true,
+            })
+            .collect())
+    }
+
+    /// the same fixture candles are served for every pair and interval
+    async fn fetch_candles(
+        &self,
+        _pair: &Pair,
+        range: std::ops::Range<u64>,
+        _interval: Interval,
+    ) -> Result<Vec<Candle>, ExchangeErrors> {
+        let candles: Vec<Candle> = Self::fixture(CANDLES)?;
+
+        Ok(candles
+            .into_iter()
+            .filter(|c| range.contains(&c.timestamp))
+            .collect())
+    }
+
+    async fn get_taker_volume(
+        &self,
+        pair: &Pair,
+        range: std::ops::Range<u64>,
+    ) -> Result<Vec<TimeVolume>, ExchangeErrors> {
+        self.fetch_candles(pair, range, Interval::OneMinute)
+            .await
+            .map(|candles| {
+                candles
+                    .into_iter()
+                    .map(|candle| TimeVolume::new(candle.timestamp, candle.volume))
+                    .collect()
+            })
+    }
+
+    async fn get_orderbook(&self, _pair: &Pair, size: u32) -> Result<OrderBook, ExchangeErrors> {
+        let mut orderbook: OrderBook = Self::fixture(ORDERBOOK)?;
+
+        orderbook.bids.truncate(size as usize);
+        orderbook.asks.truncate(size as usize);
+
+        Ok(orderbook)
+    }
+}
@@ -0,0 +1,143 @@
+use super::{Mock, BALANCES, FILLS, ORDERS};
+use crate::{
+    remote_exchanges::{
+        request::{
+            GeneralAmendOrderRequest, GeneralBalanceRequest, GeneralCancelOrderRequest,
+            GeneralFillsRequest, GeneralInstrumentsRequest, GeneralOrderDetailsRequest,
+            GeneralOrdersListRequest, GeneralPostOrderRequest,
+        },
+        response::{AmendedOrder, Balance, Fill, Order, OrderData, OrderStatus, OrdersPage},
+        ExchangeErrors, OpenData, UserData,
+    },
+    request_store::request::Response,
+};
+
+impl Mock {
+    fn orders_page(
+        request: &GeneralOrdersListRequest,
+        filter: impl Fn(&Order) -> bool,
+    ) -> Result<Response, ExchangeErrors> {
+        let orders: Vec<Order> = Self::fixture(ORDERS)?;
+
+        Ok(Response::OrdersInfo(OrdersPage {
+            orders: orders
+                .into_iter()
+                .filter(|o| o.instrument_id == request.instrument_id.to_string())
+                .filter(filter)
+                .take(request.limit() as usize)
+                .collect(),
+            next_cursor: None,
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl UserData for Mock {
+    async fn get_instruments(
+        &self,
+        request: GeneralInstrumentsRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        Ok(Response::Instruments(
+            self.get_public_instruments(request).await?,
+        ))
+    }
+
+    async fn get_balance(
+        &self,
+        request: GeneralBalanceRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let balances: Vec<Balance> = Self::fixture(BALANCES)?;
+
+        Ok(Response::Balances(
+            balances
+                .into_iter()
+                .filter(|b| match request.currency {
+                    Some(ref currencies) => currencies.contains(&b.currency),
+                    None => true,
+                })
+                .collect(),
+        ))
+    }
+
+    async fn post_order(
+        &self,
+        request: GeneralPostOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        Ok(Response::Order(OrderData {
+            id: Self::next_order_id(),
+            message: format!(
+                "{} {} {}",
+                request.side,
+                request.size,
+                request.instrument_id.to_string()
+            ),
+        }))
+    }
+
+    async fn cancel_order(
+        &self,
+        request: GeneralCancelOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        Ok(Response::Cancelled(OrderData {
+            id: request.order_id,
+            message: "".to_string(),
+        }))
+    }
+
+    async fn amend_order(
+        &self,
+        request: GeneralAmendOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        Ok(Response::Amended(AmendedOrder {
+            id: request.order_id,
+            emulated: false,
+            message: "".to_string(),
+        }))
+    }
+
+    async fn get_order_details(
+        &self,
+        request: GeneralOrderDetailsRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let orders: Vec<Order> = Self::fixture(ORDERS)?;
+
+        orders
+            .into_iter()
+            .find(|o| o.order_id == request.order_id)
+            .map(Response::OrderDetails)
+            .ok_or(ExchangeErrors::MissingOrder)
+    }
+
+    async fn get_fills(&self, request: GeneralFillsRequest) -> Result<Response, ExchangeErrors> {
+        let fills: Vec<Fill> = Self::fixture(FILLS)?;
+
+        Ok(Response::Fills(
+            fills
+                .into_iter()
+                .filter(|f| f.instrument_id == request.instrument_id.to_string())
+                .filter(|f| match request.order_id {
+                    Some(ref order_id) => &f.order_id == order_id,
+                    None => true,
+                })
+                .collect(),
+        ))
+    }
+
+    async fn get_pending_orders(
+        &self,
+        request: GeneralOrdersListRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        Self::orders_page(&request, |o| {
+            matches!(o.state, OrderStatus::Open | OrderStatus::PartiallyFilled)
+        })
+    }
+
+    async fn get_done_orders(
+        &self,
+        request: GeneralOrdersListRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        Self::orders_page(&request, |o| {
+            !matches!(o.state, OrderStatus::Open | OrderStatus::PartiallyFilled)
+        })
+    }
+}
@@ -1,26 +1,32 @@
 use crate::exchange::{Candle, TimeVolume};
+use crate::interval::Interval;
 use crate::remote_exchanges::response::Instrument;
 use crate::request_store::request::Response;
 use crate::{api_client::ApiClientErrors, Pair};
 use candid::CandidType;
 use ic_cdk::api::management_canister::http_request::{HttpHeader, HttpMethod};
 use request::{
-    GeneralBalanceRequest, GeneralInstrumentsRequest, GeneralOrdersListRequest,
-    GeneralPostOrderRequest,
+    GeneralAmendOrderRequest, GeneralBalanceRequest, GeneralCancelOrderRequest,
+    GeneralFillsRequest, GeneralInstrumentsRequest, GeneralOrderDetailsRequest,
+    GeneralOrdersListRequest, GeneralPostOrderRequest,
 };
 use response::OrderBook;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
+pub mod binance;
 pub mod coinbase;
+pub mod kraken;
+#[cfg(test)]
+pub mod mock;
 pub mod okx;
 pub mod request;
 pub mod response;
 
 #[derive(Debug, Error, CandidType)]
 pub enum ExchangeErrors {
-    #[error("api client error")]
+    #[error("api client error: {0}")]
     ApiClientError(#[from] ApiClientErrors),
     #[error("index format conversion failed")]
     InvalidIndex,
@@ -39,7 +45,27 @@ pub enum ExchangeErrors {
     #[error("missing timestamp, pair/volumes are not initialized")]
     MissingTimestamp,
     #[error("given orderbook data do not exist")]
-    MissingOrderbook
+    MissingOrderbook,
+    #[error("given order does not exist on exchange")]
+    MissingOrder,
+    #[error("exchange rejected request: {message}")]
+    RequestRejected { message: String },
+    #[error("given candle interval is not supported by exchange")]
+    UnsupportedInterval,
+    #[error("outcall quota exceeded, {message}, retry at {retry_at}")]
+    RateLimited { message: String, retry_at: u64 },
+    #[error("transaction has {expected} instructions, {received} signatures were given")]
+    SignatureCountMismatch { expected: u32, received: u32 },
+    #[error("signature timestamp {timestamp} is more than {max_skew} seconds away from {now}")]
+    ExpiredSignature {
+        timestamp: u64,
+        now: u64,
+        max_skew: u64,
+    },
+    #[error("canister signing is not permitted: {message}")]
+    SigningNotPermitted { message: String },
+    #[error("canister signing failed: {message}")]
+    SigningFailed { message: String },
 }
 
 #[async_trait::async_trait]
@@ -53,7 +79,7 @@ pub trait OpenData {
         &self,
         pair: &Pair,
         range: std::ops::Range<u64>,
-        interval: u32,
+        interval: Interval,
     ) -> Result<Vec<Candle>, ExchangeErrors>;
 
     async fn get_taker_volume(
@@ -80,6 +106,23 @@ pub trait UserData {
         request: GeneralPostOrderRequest,
     ) -> Result<Response, ExchangeErrors>;
 
+    async fn cancel_order(
+        &self,
+        request: GeneralCancelOrderRequest,
+    ) -> Result<Response, ExchangeErrors>;
+
+    async fn amend_order(
+        &self,
+        request: GeneralAmendOrderRequest,
+    ) -> Result<Response, ExchangeErrors>;
+
+    async fn get_order_details(
+        &self,
+        request: GeneralOrderDetailsRequest,
+    ) -> Result<Response, ExchangeErrors>;
+
+    async fn get_fills(&self, request: GeneralFillsRequest) -> Result<Response, ExchangeErrors>;
+
     async fn get_pending_orders(
         &self,
         request: GeneralOrdersListRequest,
@@ -153,6 +196,10 @@ pub trait ApiRequest: Serialize {
     const HOST: &'static str;
     const BODY: bool;
     const PATH_PARAMS: bool = false;
+    /// outcalls support only GET, HEAD and POST, requests with other method
+    /// are sent as POST and the proxy forwards them with the overridden one;
+    /// they are refused unless `Config::proxy_method_override` is set
+    const METHOD_OVERRIDE: Option<&'static str> = None;
 
     type Response: for<'de> Deserialize<'de>;
 
@@ -169,8 +216,73 @@ pub trait ApiRequest: Serialize {
     {
         serde_json::to_string(self).unwrap()
     }
+
+    /// parts the api client sends and the pre-sign string is made of
+    fn parts(&self) -> RequestParts
+    where
+        Self: Serialize + Sized,
+    {
+        let method = Self::METHOD_OVERRIDE.unwrap_or(match Self::METHOD {
+            HttpMethod::GET => "GET",
+            HttpMethod::POST => "POST",
+            HttpMethod::HEAD => "HEAD",
+        });
+
+        let path = match Self::PATH_PARAMS {
+            true => self.get_path(Self::URI),
+            false => Self::URI.to_string(),
+        };
+
+        let (query, body) = match Self::BODY {
+            true => ("".to_string(), Some(self.to_body())),
+            false => (self.to_query_string(), None),
+        };
+
+        RequestParts {
+            method,
+            path,
+            query,
+            body,
+        }
+    }
+}
+
+/// exchange request as it goes over the wire, without host and authentication
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestParts {
+    /// overridden method for requests sent with another one
+    pub method: &'static str,
+    pub path: String,
+    pub query: String,
+    pub body: Option<String>,
+}
+
+impl RequestParts {
+    /// path with the query string, if there is any
+    pub fn path_and_query(&self) -> String {
+        match self.query.is_empty() {
+            true => format!("/{}", self.path),
+            false => format!("/{}?{}", self.path, self.query),
+        }
+    }
+
+    /// method, path, query and body, okx and coinbase sign it prefixed by timestamp
+    pub fn signature_payload(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.method,
+            self.path_and_query(),
+            self.body.as_deref().unwrap_or_default()
+        )
+    }
 }
 
 pub trait Authorize {
     fn get_auth_headers(&self) -> Vec<HttpHeader>;
+
+    /// query parameters appended after the request query string, used by
+    /// exchanges that expect the signature in the url instead of headers
+    fn get_auth_query(&self) -> Option<String> {
+        None
+    }
 }
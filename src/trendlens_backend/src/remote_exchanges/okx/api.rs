@@ -1,7 +1,7 @@
 use std::{borrow::Cow, fmt, mem::size_of, str::FromStr};
 
 use super::response::{
-    AccountInfo, CandleStick, ConcreteInstrument, IndexCandleStick, Order, OrderBook, PlaceOrderDetails, TakerVolume
+    AccountInfo, AmendOrderDetails, CancelOrderDetails, Fill, CandleStick, ConcreteInstrument, IndexCandleStick, Order, OrderBook, PlaceOrderDetails, TakerVolume
 };
 use crate::remote_exchanges::ApiRequest;
 use candid::CandidType;
@@ -185,6 +185,70 @@ impl ApiRequest for PlaceOrderBody {
     type Response = Vec<PlaceOrderDetails>;
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, CandidType, Default)]
+pub struct CancelOrderBody {
+    #[serde(rename = "instId")]
+    pub instrument_id: String,
+    #[serde(rename = "ordId")]
+    pub order_id: Option<String>,
+    #[serde(rename = "clOrdId")]
+    pub client_order_id: Option<String>,
+}
+
+impl ApiRequest for CancelOrderBody {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "api/v5/trade/cancel-order";
+    const HOST: &'static str = "www.okx.com";
+    const BODY: bool = true;
+
+    type Response = Vec<CancelOrderDetails>;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OrderDetailsRequest {
+    #[serde(rename = "instId")]
+    pub instrument_id: String,
+    #[serde(rename = "ordId")]
+    pub order_id: Option<String>,
+    #[serde(rename = "clOrdId")]
+    pub client_order_id: Option<String>,
+}
+
+impl ApiRequest for OrderDetailsRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v5/trade/order";
+    const HOST: &'static str = "www.okx.com";
+    const BODY: bool = false;
+
+    type Response = Vec<Order>;
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, CandidType, Default)]
+pub struct AmendOrderBody {
+    #[serde(rename = "instId")]
+    pub instrument_id: String,
+    #[serde(rename = "ordId")]
+    pub order_id: Option<String>,
+    #[serde(rename = "clOrdId")]
+    pub client_order_id: Option<String>,
+    #[serde(rename = "newSz")]
+    pub new_size: Option<String>,
+    #[serde(rename = "newPx")]
+    pub new_price: Option<String>,
+}
+
+impl ApiRequest for AmendOrderBody {
+    const METHOD: HttpMethod = HttpMethod::POST;
+    const URI: &'static str = "api/v5/trade/amend-order";
+    const HOST: &'static str = "www.okx.com";
+    const BODY: bool = true;
+
+    type Response = Vec<AmendOrderDetails>;
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -219,6 +283,11 @@ pub struct PendingOrdersRequest {
     #[serde(rename = "instType")]
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub instrument_type: Option<InstrumentType>,
+    /// orders older than given ordId
+    pub after: Option<String>,
+    /// orders newer than given ordId
+    pub before: Option<String>,
+    pub limit: Option<u32>,
 }
 
 impl ApiRequest for PendingOrdersRequest {
@@ -240,6 +309,11 @@ pub struct OrdersHistoryRequest {
     #[serde(rename = "instId")]
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub instrument_id: Option<String>,
+    /// orders older than given ordId
+    pub after: Option<String>,
+    /// orders newer than given ordId
+    pub before: Option<String>,
+    pub limit: Option<u32>,
 }
 
 impl ApiRequest for OrdersHistoryRequest {
@@ -251,6 +325,29 @@ impl ApiRequest for OrdersHistoryRequest {
     type Response = Vec<Order>;
 }
 
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillsRequest {
+    #[serde(rename = "instType")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub instrument_type: Option<InstrumentType>,
+    #[serde(rename = "instId")]
+    pub instrument_id: Option<String>,
+    #[serde(rename = "ordId")]
+    pub order_id: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl ApiRequest for FillsRequest {
+    const METHOD: HttpMethod = HttpMethod::GET;
+    const URI: &'static str = "api/v5/trade/fills";
+    const HOST: &'static str = "www.okx.com";
+    const BODY: bool = false;
+
+    type Response = Vec<Fill>;
+}
+
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
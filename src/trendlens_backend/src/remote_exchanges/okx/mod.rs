@@ -1,12 +1,21 @@
-use super::request::{OrderSide, OrderType, TradeMode};
+use super::request::{
+    GeneralAmendOrderRequest, GeneralBalanceRequest, GeneralCancelOrderRequest,
+    GeneralFillsRequest, GeneralInstrumentsRequest, GeneralOrderDetailsRequest,
+    GeneralOrdersListRequest, GeneralPostOrderRequest, OrderSide, OrderType, TradeMode,
+};
+use super::response::{Order as GlobalOrder, OrdersPage};
 use super::{ApiRequest, ExchangeErrors};
 use crate::api_client::ApiClient;
 use crate::chain_data::ChainData;
 use crate::exchange::{Candle, Exchange};
+use crate::interval::Interval;
 use crate::Pair;
-use api::IndexCandleStickRequest;
+use api::{
+    AmendOrderBody, CancelOrderBody, FillsRequest, GetBalanceRequest, GetInstrumentsRequest,
+    IndexCandleStickRequest, InstrumentType, OrderDetailsRequest, OrdersHistoryRequest,
+    PendingOrdersRequest, PlaceOrderBody,
+};
 use auth::OkxAuth;
-use ic_cdk::api::management_canister::http_request::HttpMethod;
 use response::{ApiResponse, IndexCandleStick};
 
 pub mod api;
@@ -15,22 +24,30 @@ pub mod open;
 pub mod response;
 pub mod user;
 
-#[derive(Default)]
 pub struct Okx {
     auth: Option<OkxAuth>,
     api_client: ApiClient,
 }
 
+impl Default for Okx {
+    fn default() -> Self {
+        Self {
+            auth: None,
+            api_client: ApiClient::new(Exchange::Okx),
+        }
+    }
+}
+
 impl Okx {
     pub async fn fetch_index_candles(
         &self,
         pair: &Pair,
         range: std::ops::Range<u64>,
-        interval: u32,
+        interval: Interval,
     ) -> Result<Vec<Candle>, ExchangeErrors> {
         let index_name = Okx::instrument_id(pair).ok_or_else(|| ExchangeErrors::InvalidIndex)?;
 
-        ic_cdk::println!("index_name: {:?}", index_name);
+        log!("index_name: {:?}", index_name);
         let candle_request = IndexCandleStickRequest {
             after_timestamp: None,
             before_timestamp: Some(range.start * 1000),
@@ -53,12 +70,15 @@ impl Okx {
             .collect())
     }
 
-    /// gets interval string from u32 in minutes
-    fn interval_string(interval: u32) -> String {
+    /// gets okx bar size, daily bars are aligned to utc like on other exchanges
+    fn interval_string(interval: Interval) -> String {
         match interval {
-            0..=60 => "1m",
-            61..=180 => "3m",
-            181..=u32::MAX => "5m",
+            Interval::OneMinute => "1m",
+            Interval::FiveMinutes => "5m",
+            Interval::FifteenMinutes => "15m",
+            Interval::OneHour => "1H",
+            Interval::FourHours => "4H",
+            Interval::OneDay => "1Dutc",
         }
         .to_string()
     }
@@ -96,30 +116,103 @@ impl Okx {
         return Some(format!("{}-{}", pair.base, pair.quote).to_uppercase());
     }
 
-    pub fn get_signature_data<R: ApiRequest>(&self, request: R) -> String {
-        let (qs, body) = if R::BODY {
-            ("".to_string(), request.to_body())
-        } else {
-            let qs = request.to_query_string();
-
-            let qs = if qs == "" {
-                "".to_string()
-            } else {
-                format!("?{}", qs)
-            };
-
-            (qs, "".to_string())
-        };
+    // requests below are both signed and sent, build them only here
+
+    pub fn instruments_request(request: &GeneralInstrumentsRequest) -> GetInstrumentsRequest {
+        GetInstrumentsRequest {
+            instrument_type: request.instrument_type,
+            instrument_id: request.instrument_id.as_ref().and_then(Okx::instrument_id),
+        }
+    }
+
+    pub fn balance_request(request: &GeneralBalanceRequest) -> GetBalanceRequest {
+        GetBalanceRequest {
+            currencies: request.currency.as_ref().map(|c| c.join(",")),
+        }
+    }
+
+    pub fn place_order_request(request: &GeneralPostOrderRequest) -> PlaceOrderBody {
+        PlaceOrderBody {
+            side: Okx::side_string(request.side),
+            instrument_id: request.instrument_id.to_string(),
+            order_type: Okx::order_type_string(request.order_type),
+            size: request.size.to_string(),
+            order_price: request.order_price.map(|p| p.to_string()),
+            trade_mode: Okx::trade_mode_string(request.trade_mode),
+            ..Default::default()
+        }
+    }
+
+    pub fn cancel_order_request(request: &GeneralCancelOrderRequest) -> CancelOrderBody {
+        CancelOrderBody {
+            instrument_id: request.instrument_id.to_string(),
+            order_id: Some(request.order_id.clone()),
+            ..Default::default()
+        }
+    }
+
+    pub fn amend_order_request(request: &GeneralAmendOrderRequest) -> AmendOrderBody {
+        AmendOrderBody {
+            instrument_id: request.instrument_id.to_string(),
+            order_id: Some(request.order_id.clone()),
+            new_size: Some(request.size.to_string()),
+            new_price: Some(request.price.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn order_details_request(request: &GeneralOrderDetailsRequest) -> OrderDetailsRequest {
+        OrderDetailsRequest {
+            instrument_id: request.instrument_id.to_string(),
+            order_id: Some(request.order_id.clone()),
+            ..Default::default()
+        }
+    }
 
-        let method_str = if R::METHOD == HttpMethod::GET {
-            "GET"
-        } else {
-            "POST"
+    pub fn fills_request(request: &GeneralFillsRequest) -> FillsRequest {
+        FillsRequest {
+            instrument_type: Some(InstrumentType::Spot),
+            instrument_id: Some(request.instrument_id.to_string()),
+            order_id: request.order_id.clone(),
+            limit: Some(100),
+        }
+    }
+
+    pub fn pending_orders_request(request: &GeneralOrdersListRequest) -> PendingOrdersRequest {
+        PendingOrdersRequest {
+            instrument_id: Some(request.instrument_id.to_string()),
+            instrument_type: Some(request.instrument_type),
+            after: request.cursor.clone(),
+            before: None,
+            limit: Some(request.limit()),
+        }
+    }
+
+    pub fn orders_history_request(request: &GeneralOrdersListRequest) -> OrdersHistoryRequest {
+        OrdersHistoryRequest {
+            instrument_id: Some(request.instrument_id.to_string()),
+            instrument_type: request.instrument_type,
+            after: request.cursor.clone(),
+            before: None,
+            limit: Some(request.limit()),
+        }
+    }
+
+    /// okx pages by ordId, a full page means older orders may follow
+    pub fn orders_page(orders: Vec<GlobalOrder>, limit: u32) -> OrdersPage {
+        let next_cursor = match orders.len() >= limit as usize {
+            true => orders.last().map(|o| o.order_id.clone()),
+            false => None,
         };
 
-        let api_url = format!("/{}{}", R::URI, qs);
+        OrdersPage {
+            orders,
+            next_cursor,
+        }
+    }
 
-        format!("{}{}{}", method_str, api_url, body).to_string()
+    pub fn get_signature_data<R: ApiRequest>(&self, request: R) -> String {
+        request.parts().signature_payload()
     }
 }
 
@@ -132,16 +225,50 @@ impl ChainData for Okx {
 #[cfg(test)]
 mod test_okx_helpers {
     use super::*;
+    use crate::{api_client::transport::TransportKind, remote_exchanges::OpenData};
+    use futures::executor::block_on;
+    use std::str::FromStr;
 
     #[test]
     fn test_okx_interval_string() {
-        assert_eq!(Okx::interval_string(0), "1m");
-        assert_eq!(Okx::interval_string(1), "1m");
-        assert_eq!(Okx::interval_string(2), "3m");
-        assert_eq!(Okx::interval_string(3), "3m");
-        assert_eq!(Okx::interval_string(4), "5m");
-        assert_eq!(Okx::interval_string(5), "5m");
-        assert_eq!(Okx::interval_string(50), "5m");
-        assert_eq!(Okx::interval_string(u32::MAX), "5m");
+        assert_eq!(Okx::interval_string(Interval::OneMinute), "1m");
+        assert_eq!(Okx::interval_string(Interval::FiveMinutes), "5m");
+        assert_eq!(Okx::interval_string(Interval::FifteenMinutes), "15m");
+        assert_eq!(Okx::interval_string(Interval::OneHour), "1H");
+        assert_eq!(Okx::interval_string(Interval::FourHours), "4H");
+        assert_eq!(Okx::interval_string(Interval::OneDay), "1Dutc");
+    }
+
+    #[test]
+    fn test_taker_volume_only_for_usdt_pair() {
+        // nothing is recorded, a request would fail
+        TransportKind::set(Exchange::Okx, TransportKind::Replay);
+
+        let volumes =
+            block_on(Okx::default().get_taker_volume(&Pair::from_str("eth-btc").unwrap(), 0..3600))
+                .unwrap();
+
+        assert!(volumes.is_empty());
+    }
+
+    #[test]
+    fn test_orders_history_cursor() {
+        let request = GeneralOrdersListRequest {
+            instrument_type: api::InstrumentType::Spot,
+            instrument_id: Pair {
+                base: "BTC".to_string(),
+                quote: "USDT".to_string(),
+            },
+            pending: false,
+            cursor: Some("590908157585625111".to_string()),
+            limit: Some(2),
+        };
+
+        let okx = Okx::default();
+
+        assert_eq!(
+            okx.get_signature_data(Okx::orders_history_request(&request)),
+            "GET/api/v5/trade/orders-history?instType=SPOT&instId=BTC-USDT&after=590908157585625111&limit=2"
+        );
     }
 }
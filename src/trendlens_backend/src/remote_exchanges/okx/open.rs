@@ -1,8 +1,9 @@
-use super::api::{GetOrderBookRequest, SpotCandleStickRequest};
+use super::api::{GetOrderBookRequest, InstrumentType, SpotCandleStickRequest, TakerVolumeRequest};
 use super::auth::OkxAuth;
-use super::response::{ApiResponse, CandleStick, ConcreteInstrument, OrderBook};
+use super::response::{ApiResponse, CandleStick, ConcreteInstrument, OrderBook, TakerVolume};
 use super::Okx;
 use crate::exchange::TimeVolume;
+use crate::interval::Interval;
 use crate::remote_exchanges::request::GeneralInstrumentsRequest;
 use crate::remote_exchanges::response::{Instrument, OrderBook as GlobalOrderBook};
 use crate::{
@@ -12,6 +13,8 @@ use crate::{
 };
 
 const MAX_RESPONSE_CANDLES_COUNT: u32 = 300;
+/// quote of the pairs which carry the base currency taker volume
+const TAKER_VOLUME_QUOTE: &str = "usdt";
 
 #[async_trait::async_trait]
 impl OpenData for Okx {
@@ -19,20 +22,20 @@ impl OpenData for Okx {
         &self,
         pair: &Pair,
         range: std::ops::Range<u64>,
-        interval: u32,
+        interval: Interval,
     ) -> Result<Vec<Candle>, ExchangeErrors> {
         let mut current = range.start;
         let mut responses = vec![];
 
-        ic_cdk::println!("Coinbase: fetching range {:?}", range);
+        log!("Okx: fetching range {:?}", range);
 
         while current < range.end {
             let end = u64::min(
-                current + (interval * MAX_RESPONSE_CANDLES_COUNT) as u64,
+                current + interval.seconds() * MAX_RESPONSE_CANDLES_COUNT as u64,
                 range.end,
             );
 
-            ic_cdk::println!("Coinbase: fetching from {} to {}", current, end);
+            log!("Okx: fetching from {} to {}", current, end);
 
             let request = SpotCandleStickRequest {
                 end: Some(end * 1000),
@@ -54,7 +57,7 @@ impl OpenData for Okx {
 
             current = end + 1;
 
-            ic_cdk::println!("c {} e {}", current, range.end)
+            log!("c {} e {}", current, range.end)
         }
 
         Ok(responses
@@ -63,20 +66,39 @@ impl OpenData for Okx {
             .collect())
     }
 
+    /// taker volume is aggregated by okx over all spot pairs of the base currency,
+    /// it is returned only for the usdt pair so it is not stored once per quote
     async fn get_taker_volume(
         &self,
         pair: &Pair,
         range: std::ops::Range<u64>,
     ) -> Result<Vec<TimeVolume>, ExchangeErrors> {
-        let candles = self.fetch_candles(pair, range, 300).await.unwrap();
+        if !pair.quote.eq_ignore_ascii_case(TAKER_VOLUME_QUOTE) {
+            log!(
+                "Okx: taker volume of {} is kept under its usdt pair",
+                pair.to_string()
+            );
 
-        Ok(candles
-            .into_iter()
-            .map(|candle| TimeVolume {
-                timestamp: candle.timestamp,
-                volume: candle.volume,
-            })
-            .collect())
+            return Ok(vec![]);
+        }
+
+        let request = TakerVolumeRequest {
+            currency: pair.base.to_uppercase(),
+            instrument_type: Some(InstrumentType::Spot),
+            begin: Some(range.start * 1000),
+            end: Some(range.end * 1000),
+            period: Some(Okx::interval_string(Interval::FiveMinutes)),
+        };
+
+        let response = self
+            .api_client
+            .call::<ApiResponse<Vec<TakerVolume>>, TakerVolumeRequest, OkxAuth>(
+                request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(response.into_iter().map(|v| v.into()).collect())
     }
 
     async fn get_public_instruments(
@@ -3,13 +3,14 @@ use serde_this_or_that::as_u64;
 use std::{fmt::Display, str::FromStr};
 
 use crate::{
-    exchange::Candle,
+    exchange::{Candle, TimeVolume},
     pair::Pair,
     remote_exchanges::{
         request::{OrderSide, OrderType, TradeMode},
         response::{
-            ApiResponseWrapper, Balance, BidAsk as GlobalBidAsk, Instrument,
-            OrderBook as GlobalOrderBook,
+            ApiResponseWrapper, Balance, BidAsk as GlobalBidAsk, Fill as GlobalFill, Instrument,
+            Liquidity, Order as GlobalOrder, OrderBook as GlobalOrderBook,
+            OrderStatus as GlobalOrderStatus, ReportedOrderType,
         },
         ExchangeErrors,
     },
@@ -167,6 +168,32 @@ pub struct PlaceOrderDetails {
     pub status_message: String,
 }
 
+#[derive(Serialize, Deserialize, CandidType, Debug, Clone)]
+pub struct CancelOrderDetails {
+    #[serde(rename = "ordId")]
+    pub order_id: String,
+    #[serde(rename = "clOrdId")]
+    pub client_order_id: String,
+    #[serde(rename = "sCode")]
+    pub status_code: String,
+    #[serde(rename = "sMsg")]
+    pub status_message: String,
+}
+
+#[derive(Serialize, Deserialize, CandidType, Debug, Clone)]
+pub struct AmendOrderDetails {
+    #[serde(rename = "ordId")]
+    pub order_id: String,
+    #[serde(rename = "clOrdId")]
+    pub client_order_id: String,
+    #[serde(rename = "reqId")]
+    pub request_id: String,
+    #[serde(rename = "sCode")]
+    pub status_code: String,
+    #[serde(rename = "sMsg")]
+    pub status_message: String,
+}
+
 #[serde_as]
 #[derive(Serialize, Deserialize, CandidType, Debug, Clone)]
 pub struct Order {
@@ -188,8 +215,7 @@ pub struct Order {
     #[serde_as(as = "DisplayFromStr")]
     pub side: OrderSide,
     #[serde(rename = "ordType")]
-    #[serde_as(as = "DisplayFromStr")]
-    pub order_type: OrderType,
+    pub order_type: String,
     #[serde(rename = "tdMode")]
     #[serde_as(as = "DisplayFromStr")]
     pub trade_mode: TradeMode,
@@ -199,10 +225,90 @@ pub struct Order {
     #[serde(rename = "avgPx")]
     #[serde(deserialize_with = "as_f64")]
     pub average_filled_price: f64,
+    #[serde(deserialize_with = "as_f64")]
+    pub fee: f64,
+    #[serde(rename = "feeCcy")]
+    pub fee_currency: String,
     #[serde_as(as = "DisplayFromStr")]
     pub state: OrderState,
 }
 
+impl From<Order> for GlobalOrder {
+    fn from(value: Order) -> Self {
+        GlobalOrder {
+            instrument_type: value.instrument_type.to_string(),
+            instrument_id: value.instrument_id,
+            order_id: value.order_id,
+            price: value.price,
+            size: value.size,
+            side: value.side,
+            order_type: match OrderType::from_str(&value.order_type) {
+                Ok(order_type) => ReportedOrderType::Common(order_type),
+                Err(_) => ReportedOrderType::Other(value.order_type),
+            },
+            trade_mode: value.trade_mode.to_string(),
+            accumulated_fill_quantity: value.accumulated_fill_quantity,
+            average_price: (value.accumulated_fill_quantity > 0.0)
+                .then_some(value.average_filled_price),
+            // okx reports charged fees as negative numbers
+            fee: Some(-value.fee),
+            fee_currency: (!value.fee_currency.is_empty()).then_some(value.fee_currency),
+            state: value.state.into(),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Fill {
+    #[serde(rename = "instId")]
+    pub instrument_id: String,
+    #[serde(rename = "tradeId")]
+    pub trade_id: String,
+    #[serde(rename = "ordId")]
+    pub order_id: String,
+    #[serde(rename = "fillPx")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub fill_price: f64,
+    #[serde(rename = "fillSz")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub fill_size: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub side: OrderSide,
+    /// T for taker, M for maker
+    #[serde(rename = "execType")]
+    pub execution_type: String,
+    #[serde(deserialize_with = "as_f64")]
+    pub fee: f64,
+    #[serde(rename = "feeCcy")]
+    pub fee_currency: String,
+    #[serde(rename = "ts")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub timestamp: u64,
+}
+
+impl From<Fill> for GlobalFill {
+    fn from(value: Fill) -> Self {
+        GlobalFill {
+            instrument_id: value.instrument_id,
+            order_id: value.order_id,
+            trade_id: value.trade_id,
+            side: value.side,
+            price: value.fill_price,
+            size: value.fill_size,
+            // okx reports charged fees as negative numbers
+            fee: -value.fee,
+            fee_currency: value.fee_currency,
+            liquidity: match value.execution_type.as_str() {
+                "T" => Some(Liquidity::Taker),
+                "M" => Some(Liquidity::Maker),
+                _ => None,
+            },
+            timestamp: value.timestamp / 1000,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, CandidType, Debug, Clone)]
 pub enum OrderState {
     Pending,
@@ -214,6 +320,19 @@ pub enum OrderState {
     PartiallyFilled,
 }
 
+impl From<OrderState> for GlobalOrderStatus {
+    fn from(value: OrderState) -> Self {
+        match value {
+            OrderState::Pending | OrderState::Live => GlobalOrderStatus::Open,
+            OrderState::PartiallyFilled => GlobalOrderStatus::PartiallyFilled,
+            OrderState::Filled => GlobalOrderStatus::Filled,
+            OrderState::Done => GlobalOrderStatus::Closed,
+            // market maker protection cancels are still cancellations
+            OrderState::Canceled | OrderState::MmpCanceled => GlobalOrderStatus::Cancelled,
+        }
+    }
+}
+
 impl FromStr for OrderState {
     type Err = String;
 
@@ -412,16 +531,78 @@ mod tests {
     }
 }
 
+// okx sends entries as [ts, sellVol, buyVol] arrays, fields follow that order
 #[serde_as]
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct TakerVolume {
     #[serde(rename = "ts")]
     #[serde_as(as = "DisplayFromStr")]
     pub timestamp: u64,
-    #[serde(rename = "buyVol")]
-    #[serde_as(as = "DisplayFromStr")]
-    pub buy_volume: f64,
     #[serde(rename = "sellVol")]
     #[serde_as(as = "DisplayFromStr")]
     pub sell_volume: f64,
+    #[serde(rename = "buyVol")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub buy_volume: f64,
+}
+
+impl From<TakerVolume> for TimeVolume {
+    fn from(value: TakerVolume) -> Self {
+        TimeVolume::with_split(value.timestamp / 1000, value.buy_volume, value.sell_volume)
+    }
+}
+
+#[cfg(test)]
+mod taker_volume_tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_taker_volume() {
+        let response =
+            r#"{"code":"0","msg":"","data":[["1630425600000","7596.2651","7149.4855"]]}"#;
+        let response: ApiResponse<Vec<TakerVolume>> = serde_json::from_str(response).unwrap();
+        let volume: TimeVolume = response.data[0].clone().into();
+
+        assert_eq!(volume.timestamp, 1630425600);
+        assert_eq!(volume.sell_volume, Some(7596.2651));
+        assert_eq!(volume.buy_volume, Some(7149.4855));
+        assert!(volume.imbalance.unwrap() < 0.0);
+    }
+}
+
+#[cfg(test)]
+mod order_tests {
+    use super::*;
+
+    #[test]
+    fn test_order_conversion() {
+        let response = r#"{"code":"0","msg":"","data":[{"instType":"SPOT","instId":"BTC-USDT","tgtCcy":"","ordId":"680800019749904384","px":"30000","sz":"0.02","side":"buy","ordType":"limit","tdMode":"cash","accFillSz":"0.01","avgPx":"29950.5","fee":"-0.00001","feeCcy":"BTC","state":"partially_filled"}]}"#;
+        let response: ApiResponse<Vec<Order>> = serde_json::from_str(response).unwrap();
+
+        let order: GlobalOrder = response.data[0].clone().into();
+
+        assert_eq!(order.order_id, "680800019749904384");
+        assert_eq!(order.average_price, Some(29950.5));
+        assert_eq!(order.fee, Some(0.00001));
+        assert_eq!(order.fee_currency, Some("BTC".to_string()));
+        assert_eq!(order.state, GlobalOrderStatus::PartiallyFilled);
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(
+            order.order_type,
+            ReportedOrderType::Common(OrderType::Limit)
+        );
+    }
+
+    #[test]
+    fn test_fill_conversion() {
+        let response = r#"{"code":"0","msg":"","data":[{"instType":"SPOT","instId":"BTC-USDT","tradeId":"123","ordId":"312269865356374016","clOrdId":"","billId":"16","tag":"","fillPx":"30000","fillSz":"0.01","side":"buy","posSide":"net","execType":"M","feeCcy":"USDT","fee":"0.03","ts":"1597026383085"}]}"#;
+        let response: ApiResponse<Vec<Fill>> = serde_json::from_str(response).unwrap();
+
+        let fill: GlobalFill = response.data[0].clone().into();
+
+        assert_eq!(fill.trade_id, "123");
+        assert_eq!(fill.fee, -0.03);
+        assert_eq!(fill.liquidity, Some(Liquidity::Maker));
+        assert_eq!(fill.timestamp, 1597026383);
+    }
 }
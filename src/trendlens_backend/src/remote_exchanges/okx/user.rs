@@ -1,21 +1,23 @@
 use super::{
     api::{
-        GetBalanceRequest, GetInstrumentsRequest, OrdersHistoryRequest, PendingOrdersRequest,
-        PlaceOrderBody,
+        AmendOrderBody, CancelOrderBody, FillsRequest, GetBalanceRequest, GetInstrumentsRequest,
+        OrderDetailsRequest, OrdersHistoryRequest, PendingOrdersRequest, PlaceOrderBody,
     },
     auth::OkxAuth,
     response::{
-        AccountInfo, ApiResponse, ConcreteInstrument, Order as OkxOrder, PlaceOrderDetails,
+        AccountInfo, AmendOrderDetails, ApiResponse, CancelOrderDetails, ConcreteInstrument,
+        Fill as OkxFill, Order as OkxOrder, PlaceOrderDetails,
     },
     Okx,
 };
 use crate::{
     remote_exchanges::{
         request::{
-            GeneralBalanceRequest, GeneralInstrumentsRequest, GeneralOrdersListRequest,
-            GeneralPostOrderRequest,
+            GeneralAmendOrderRequest, GeneralBalanceRequest, GeneralCancelOrderRequest,
+            GeneralFillsRequest, GeneralInstrumentsRequest, GeneralOrderDetailsRequest,
+            GeneralOrdersListRequest, GeneralPostOrderRequest,
         },
-        response::{Balance, Instrument, Order, OrderData},
+        response::{AmendedOrder, Balance, Instrument, OrderData},
         ExchangeErrors, UserData,
     },
     request_store::request::Response,
@@ -27,10 +29,7 @@ impl UserData for Okx {
         &self,
         req: GeneralInstrumentsRequest,
     ) -> Result<Response, ExchangeErrors> {
-        let exchange_request = GetInstrumentsRequest {
-            instrument_type: req.instrument_type,
-            instrument_id: req.instrument_id.and_then(|p| Self::instrument_id(&p)),
-        };
+        let exchange_request = Self::instruments_request(&req);
 
         let instrument_response = self
             .api_client
@@ -55,8 +54,7 @@ impl UserData for Okx {
         &self,
         request: GeneralBalanceRequest,
     ) -> Result<Response, ExchangeErrors> {
-        let currencies = request.currency.and_then(|c| Some(c.join(",")));
-        let exchange_request = GetBalanceRequest { currencies };
+        let exchange_request = Self::balance_request(&request);
 
         let balances_response = self
             .api_client
@@ -78,15 +76,7 @@ impl UserData for Okx {
         &self,
         request: GeneralPostOrderRequest,
     ) -> Result<Response, ExchangeErrors> {
-        let exchange_request = PlaceOrderBody {
-            side: Self::side_string(request.side),
-            instrument_id: request.instrument_id.to_string(),
-            order_type: Self::order_type_string(request.order_type),
-            size: request.size.to_string(),
-            order_price: request.order_price.map(|p| p.to_string()),
-            trade_mode: Self::trade_mode_string(request.trade_mode),
-            ..Default::default()
-        };
+        let exchange_request = Self::place_order_request(&request);
 
         let order_response = self
             .api_client
@@ -102,14 +92,90 @@ impl UserData for Okx {
         }))
     }
 
+    async fn cancel_order(
+        &self,
+        request: GeneralCancelOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::cancel_order_request(&request);
+
+        let cancel_response = self
+            .api_client
+            .call::<ApiResponse<Vec<CancelOrderDetails>>, CancelOrderBody, OkxAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Cancelled(OrderData {
+            id: cancel_response[0].order_id.clone(),
+            message: cancel_response[0].status_message.clone(),
+        }))
+    }
+
+    async fn amend_order(
+        &self,
+        request: GeneralAmendOrderRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::amend_order_request(&request);
+
+        let amend_response = self
+            .api_client
+            .call::<ApiResponse<Vec<AmendOrderDetails>>, AmendOrderBody, OkxAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Amended(AmendedOrder {
+            id: amend_response[0].order_id.clone(),
+            emulated: false,
+            message: amend_response[0].status_message.clone(),
+        }))
+    }
+
+    async fn get_order_details(
+        &self,
+        request: GeneralOrderDetailsRequest,
+    ) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::order_details_request(&request);
+
+        let order_response = self
+            .api_client
+            .call::<ApiResponse<Vec<OkxOrder>>, OrderDetailsRequest, OkxAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        let order = order_response
+            .into_iter()
+            .next()
+            .ok_or(ExchangeErrors::MissingOrder)?;
+
+        Ok(Response::OrderDetails(order.into()))
+    }
+
+    async fn get_fills(&self, request: GeneralFillsRequest) -> Result<Response, ExchangeErrors> {
+        let exchange_request = Self::fills_request(&request);
+
+        let fills_response = self
+            .api_client
+            .call::<ApiResponse<Vec<OkxFill>>, FillsRequest, OkxAuth>(
+                exchange_request,
+                self.auth.as_ref(),
+            )
+            .await?;
+
+        Ok(Response::Fills(
+            fills_response.into_iter().map(Into::into).collect(),
+        ))
+    }
+
     async fn get_pending_orders(
         &self,
         request: GeneralOrdersListRequest,
     ) -> Result<Response, ExchangeErrors> {
-        let exchange_request = PendingOrdersRequest {
-            instrument_id: Some(request.instrument_id.to_string()),
-            instrument_type: Some(request.instrument_type),
-        };
+        let exchange_request = Self::pending_orders_request(&request);
 
         let order_response = self
             .api_client
@@ -119,33 +185,17 @@ impl UserData for Okx {
             )
             .await?;
 
-        Ok(Response::OrdersInfo(
-            order_response
-                .into_iter()
-                .map(|o| Order {
-                    instrument_type: o.instrument_type.to_string(),
-                    instrument_id: o.instrument_id,
-                    order_id: o.order_id,
-                    price: o.price,
-                    size: o.size,
-                    side: o.side.to_string(),
-                    order_type: o.order_type.to_string(),
-                    trade_mode: o.trade_mode.to_string(),
-                    accumulated_fill_quantity: o.accumulated_fill_quantity,
-                    state: o.state.to_string(),
-                })
-                .collect(),
-        ))
+        Ok(Response::OrdersInfo(Self::orders_page(
+            order_response.into_iter().map(Into::into).collect(),
+            request.limit(),
+        )))
     }
 
     async fn get_done_orders(
         &self,
         request: GeneralOrdersListRequest,
     ) -> Result<Response, ExchangeErrors> {
-        let exchange_request = OrdersHistoryRequest {
-            instrument_id: Some(request.instrument_id.to_string()),
-            instrument_type: request.instrument_type,
-        };
+        let exchange_request = Self::orders_history_request(&request);
 
         let order_response = self
             .api_client
@@ -155,22 +205,9 @@ impl UserData for Okx {
             )
             .await?;
 
-        Ok(Response::OrdersInfo(
-            order_response
-                .into_iter()
-                .map(|o| Order {
-                    instrument_type: o.instrument_type.to_string(),
-                    instrument_id: o.instrument_id,
-                    order_id: o.order_id,
-                    price: o.price,
-                    size: o.size,
-                    side: o.side.to_string(),
-                    order_type: o.order_type.to_string(),
-                    trade_mode: o.trade_mode.to_string(),
-                    accumulated_fill_quantity: o.accumulated_fill_quantity,
-                    state: o.state.to_string(),
-                })
-                .collect(),
-        ))
+        Ok(Response::OrdersInfo(Self::orders_page(
+            order_response.into_iter().map(Into::into).collect(),
+            request.limit(),
+        )))
     }
 }
@@ -4,7 +4,7 @@ use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use std::{fmt::Display, str::FromStr};
 
-#[derive(Debug, Clone, Deserialize, Serialize, CandidType, Copy)]
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, Copy, PartialEq)]
 pub enum OrderSide {
     Buy,
     Sell,
@@ -73,7 +73,7 @@ impl FromStr for TradeMode {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, CandidType, Copy)]
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, Copy, PartialEq)]
 pub enum OrderType {
     Market,
     Limit,
@@ -122,6 +122,36 @@ pub struct GeneralPostOrderRequest {
     pub order_price: Option<f64>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType)]
+pub struct GeneralCancelOrderRequest {
+    pub instrument_id: Pair,
+    pub order_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType)]
+pub struct GeneralOrderDetailsRequest {
+    pub instrument_id: Pair,
+    pub order_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType)]
+pub struct GeneralFillsRequest {
+    pub instrument_id: Pair,
+    /// limits fills to single order, otherwise recent fills of the instrument
+    pub order_id: Option<String>,
+}
+
+/// moves resting limit order to new price and size, exchanges without
+/// native amendment cancel the order and place a new one
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType)]
+pub struct GeneralAmendOrderRequest {
+    pub instrument_id: Pair,
+    pub order_id: String,
+    pub side: OrderSide,
+    pub size: f64,
+    pub price: f64,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, CandidType)]
 pub struct GeneralInstrumentsRequest {
     pub instrument_type: InstrumentType,
@@ -138,4 +168,36 @@ pub struct GeneralOrdersListRequest {
     pub instrument_type: InstrumentType,
     pub instrument_id: Pair,
     pub pending: bool,
+    /// next cursor of the previous page, continues with older orders, or
+    /// with newer ones on binance which pages from the first order
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl GeneralOrdersListRequest {
+    pub const DEFAULT_LIMIT: u32 = 100;
+
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(Self::DEFAULT_LIMIT)
+    }
+}
+
+/// orders list stored before pagination
+#[derive(Deserialize, Serialize)]
+pub struct GeneralOrdersListRequestV1 {
+    pub instrument_type: InstrumentType,
+    pub instrument_id: Pair,
+    pub pending: bool,
+}
+
+impl From<GeneralOrdersListRequestV1> for GeneralOrdersListRequest {
+    fn from(value: GeneralOrdersListRequestV1) -> Self {
+        Self {
+            instrument_type: value.instrument_type,
+            instrument_id: value.instrument_id,
+            pending: value.pending,
+            cursor: None,
+            limit: None,
+        }
+    }
 }
@@ -1,4 +1,8 @@
-use super::{okx::api::InstrumentType, ExchangeErrors};
+use super::{
+    okx::api::InstrumentType,
+    request::{OrderSide, OrderType},
+    ExchangeErrors,
+};
 use crate::pair::Pair;
 use candid::CandidType;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -19,6 +23,44 @@ pub struct OrderData {
     pub message: String,
 }
 
+#[derive(Deserialize, Debug, Clone, CandidType, Serialize)]
+pub struct AmendedOrder {
+    /// id of the order after amendment, differs from the original one
+    /// when the amendment was emulated by cancel and replace
+    pub id: String,
+    pub emulated: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, CandidType, Serialize, PartialEq)]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+#[derive(Deserialize, Debug, Clone, CandidType, Serialize)]
+pub struct Fill {
+    pub instrument_id: String,
+    pub order_id: String,
+    pub trade_id: String,
+    pub side: OrderSide,
+    pub price: f64,
+    pub size: f64,
+    /// fee paid for the fill, negative value is a rebate
+    pub fee: f64,
+    pub fee_currency: String,
+    /// missing when exchange does not report the side of the book
+    pub liquidity: Option<Liquidity>,
+    pub timestamp: u64,
+}
+
+#[derive(Deserialize, Debug, Clone, CandidType, Serialize)]
+pub struct OrdersPage {
+    pub orders: Vec<Order>,
+    /// passed as cursor to fetch the next page, missing on the last one
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Deserialize, Debug, Clone, CandidType, Serialize)]
 pub struct Balance {
     pub currency: String,
@@ -40,16 +82,42 @@ pub struct OrderBook {
     pub asks: Vec<BidAsk>,
 }
 
+/// order lifecycle state common to all exchanges
+#[derive(Deserialize, Debug, Clone, Copy, CandidType, Serialize, PartialEq)]
+pub enum OrderStatus {
+    Open,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Rejected,
+    Expired,
+    /// finished, but the exchange did not report whether it was filled or cancelled
+    Closed,
+}
+
+/// order type as reported by the exchange
+#[derive(Deserialize, Debug, Clone, CandidType, Serialize, PartialEq)]
+pub enum ReportedOrderType {
+    Common(OrderType),
+    /// types without common equivalent, like stop orders, under the exchange's own name
+    Other(String),
+}
+
 #[derive(Deserialize, Debug, Clone, CandidType, Serialize)]
 pub struct Order {
     pub instrument_type: String,
     pub instrument_id: String,
     pub order_id: String,
-    pub state: String,
+    pub state: OrderStatus,
     pub price: f64,
     pub size: f64,
-    pub side: String,
-    pub order_type: String,
+    pub side: OrderSide,
+    pub order_type: ReportedOrderType,
     pub trade_mode: String,
     pub accumulated_fill_quantity: f64,
+    /// average price of the filled part, missing when nothing was filled
+    pub average_price: Option<f64>,
+    /// fees paid for fills, negative value is a rebate
+    pub fee: Option<f64>,
+    pub fee_currency: Option<String>,
 }
@@ -1,21 +1,73 @@
 use crate::{
     exchange::Exchange,
     memory::{Memory, MemoryLocation, MEMORY_MANAGER},
-    storable_wrapper::StorableWrapper,
+    storable_wrapper::{StorableWrapper, Versioned},
 };
 use candid::{CandidType, Principal};
-use ic_stable_structures::StableBTreeMap;
-use request::Request;
+use ic_stable_structures::{StableBTreeMap, StableCell};
+use request::{Request, RequestV1};
 use serde::{Deserialize, Serialize};
 use std::{cell::RefCell, ops::Deref};
 
 pub mod request;
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, CandidType, PartialEq)]
+pub enum InstructionStatus {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, CandidType)]
 pub struct SignableInstruction {
     pub instruction: Instruction,
     pub signature: String,
-    pub executed: bool,
+    pub status: InstructionStatus,
+    /// error returned by the last failed attempt
+    pub error: Option<String>,
+    pub attempts: u32,
+}
+
+impl SignableInstruction {
+    pub fn new(instruction: Instruction, signature: String) -> Self {
+        Self {
+            instruction,
+            signature,
+            status: InstructionStatus::Pending,
+            error: None,
+            attempts: 0,
+        }
+    }
+
+    pub fn executed(&self) -> bool {
+        self.status == InstructionStatus::Succeeded
+    }
+}
+
+/// instructions stored before execution status was tracked
+#[derive(Deserialize, Serialize)]
+struct SignableInstructionV1 {
+    instruction: InstructionV1,
+    signature: String,
+    executed: bool,
+}
+
+/// instruction of the first schema version, requests added later were not stored yet
+#[derive(Deserialize, Serialize)]
+struct InstructionV1 {
+    exchange: Exchange,
+    api_key: String,
+    request: RequestV1,
+}
+
+impl From<InstructionV1> for Instruction {
+    fn from(value: InstructionV1) -> Self {
+        Self {
+            exchange: value.exchange,
+            api_key: value.api_key,
+            request: value.request.into(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, CandidType)]
@@ -37,6 +89,28 @@ impl Deref for Transaction {
     }
 }
 
+impl Versioned for SignableInstruction {
+    const VERSION: u16 = 2;
+
+    fn migrate(version: u16, payload: &[u8]) -> Self {
+        match version {
+            1 => {
+                let v1: SignableInstructionV1 = bincode::deserialize(payload).unwrap();
+
+                SignableInstruction {
+                    status: match v1.executed {
+                        true => InstructionStatus::Succeeded,
+                        false => InstructionStatus::Pending,
+                    },
+                    attempts: v1.executed as u32,
+                    ..SignableInstruction::new(v1.instruction.into(), v1.signature)
+                }
+            }
+            _ => panic!("unknown instruction version {}", version),
+        }
+    }
+}
+
 type InstructionId = u32;
 type InstructionsTable =
     StableBTreeMap<InstructionId, StorableWrapper<SignableInstruction>, Memory>;
@@ -44,6 +118,9 @@ type TransactionId = u32;
 type TransactionsTable = StableBTreeMap<TransactionId, StorableWrapper<Vec<InstructionId>>, Memory>;
 type UserTransactionsTable = StableBTreeMap<Principal, StorableWrapper<Vec<TransactionId>>, Memory>;
 
+// shared by transaction instructions and user transactions lists
+impl Versioned for Vec<u32> {}
+
 thread_local! {
     static INSTRUCTIONS: RefCell<InstructionsTable> = RefCell::new(
         StableBTreeMap::init(
@@ -62,6 +139,16 @@ thread_local! {
             MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::UserTransactions.memory_id())),
         )
     );
+
+    // id of the next transaction, ids are never reused so journal entries keep pointing
+    // to the transaction they were recorded for
+    static NEXT_TRANSACTION_ID: RefCell<StableCell<TransactionId, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::TransactionCounter.memory_id())),
+            0,
+        )
+        .expect("failed to init transaction counter")
+    );
 }
 
 // consider making users and then implementing it as trait not using struct but directly
@@ -80,13 +167,24 @@ impl TransactionStore {
         })
     }
 
+    /// canisters upgraded before the counter existed continue after the last stored id
+    fn next_transaction_id() -> TransactionId {
+        let last = TRANSACTIONS.with_borrow(|k| k.last_key_value().map(|(k, _)| k + 1));
+        let transaction_id = NEXT_TRANSACTION_ID
+            .with_borrow(|c| *c.get())
+            .max(last.unwrap_or(0));
+
+        NEXT_TRANSACTION_ID
+            .with_borrow_mut(|c| c.set(transaction_id + 1))
+            .expect("failed to store transaction counter");
+
+        transaction_id
+    }
+
     fn insert_transaction(instructions: Vec<SignableInstruction>) -> TransactionId {
-        TRANSACTIONS.with_borrow_mut(|k| {
-            let transaction_id = k
-                .last_key_value()
-                .and_then(|(k, _v)| Some(k + 1))
-                .unwrap_or(0) as TransactionId;
+        let transaction_id = Self::next_transaction_id();
 
+        TRANSACTIONS.with_borrow_mut(|k| {
             let instructions_ids: Vec<InstructionId> = instructions
                 .into_iter()
                 .map(|i| Self::insert_instruction(i))
@@ -153,6 +251,25 @@ impl TransactionStore {
         )
     }
 
+    /// overwrites instructions of the transaction in place, keeping its id
+    pub fn update_transaction(
+        identity: &Principal,
+        transaction_id: TransactionId,
+        instructions: Vec<SignableInstruction>,
+    ) -> Option<()> {
+        Self::get_transaction(identity, transaction_id)?;
+
+        let instructions_ids = TRANSACTIONS.with_borrow(|k| k.get(&transaction_id))?;
+
+        INSTRUCTIONS.with_borrow_mut(|k| {
+            for (id, instruction) in instructions_ids.iter().zip(instructions) {
+                k.insert(*id, StorableWrapper(instruction));
+            }
+        });
+
+        Some(())
+    }
+
     pub fn delete_transaction(
         identity: &Principal,
         transaction_id: TransactionId,
@@ -173,3 +290,113 @@ impl TransactionStore {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pair::Pair,
+        remote_exchanges::{
+            okx::api::InstrumentType,
+            request::{GeneralOrdersListRequest, GeneralOrdersListRequestV1},
+        },
+        storable_wrapper::envelope,
+    };
+    use ic_stable_structures::Storable;
+    use std::{borrow::Cow, str::FromStr};
+
+    #[test]
+    fn test_decode_v1_instruction() {
+        let instruction = SignableInstructionV1 {
+            instruction: InstructionV1 {
+                exchange: Exchange::Okx,
+                api_key: "api_key".to_string(),
+                request: RequestV1::Empty,
+            },
+            signature: "signature".to_string(),
+            executed: true,
+        };
+
+        let bytes = envelope(1, &bincode::serialize(&instruction).unwrap());
+        let decoded: StorableWrapper<SignableInstruction> =
+            StorableWrapper::from_bytes(Cow::Owned(bytes));
+
+        assert_eq!(decoded.status, InstructionStatus::Succeeded);
+        assert_eq!(decoded.attempts, 1);
+        assert_eq!(decoded.error, None);
+    }
+
+    #[test]
+    fn test_decode_v1_orders_list() {
+        let instruction = SignableInstructionV1 {
+            instruction: InstructionV1 {
+                exchange: Exchange::Coinbase,
+                api_key: "api_key".to_string(),
+                request: RequestV1::OrdersList(GeneralOrdersListRequestV1 {
+                    instrument_type: InstrumentType::Spot,
+                    instrument_id: Pair::from_str("btc-usd").unwrap(),
+                    pending: true,
+                }),
+            },
+            signature: "signature".to_string(),
+            executed: false,
+        };
+
+        let bytes = envelope(1, &bincode::serialize(&instruction).unwrap());
+        let decoded: StorableWrapper<SignableInstruction> =
+            StorableWrapper::from_bytes(Cow::Owned(bytes));
+
+        match &decoded.instruction.request {
+            Request::OrdersList(r) => {
+                assert!(r.pending);
+                assert_eq!(r.cursor, None);
+                assert_eq!(r.limit(), GeneralOrdersListRequest::DEFAULT_LIMIT);
+            }
+            r => panic!("decoded as {:?}", r),
+        }
+        assert_eq!(decoded.status, InstructionStatus::Pending);
+    }
+
+    #[test]
+    fn test_orders_list_keeps_baseline_index() {
+        let pair = Pair::from_str("btc-usd").unwrap();
+        let baseline = bincode::serialize(&RequestV1::OrdersList(GeneralOrdersListRequestV1 {
+            instrument_type: InstrumentType::Spot,
+            instrument_id: pair.clone(),
+            pending: false,
+        }))
+        .unwrap();
+        let current = bincode::serialize(&Request::OrdersList(GeneralOrdersListRequest {
+            instrument_type: InstrumentType::Spot,
+            instrument_id: pair,
+            pending: false,
+            cursor: None,
+            limit: None,
+        }))
+        .unwrap();
+
+        assert_eq!(baseline[..4], 4u32.to_le_bytes());
+        assert_eq!(current[..4], baseline[..4]);
+    }
+
+    #[test]
+    fn test_transaction_ids_are_not_reused() {
+        let identity = Principal::anonymous();
+        let instruction = || {
+            SignableInstruction::new(
+                Instruction {
+                    exchange: Exchange::Mock,
+                    api_key: "api_key".to_string(),
+                    request: Request::Empty,
+                },
+                "".to_string(),
+            )
+        };
+
+        let first = TransactionStore::add_transaction(&identity, vec![instruction()]);
+        TransactionStore::delete_transaction(&identity, first);
+        let second = TransactionStore::add_transaction(&identity, vec![instruction()]);
+
+        assert_ne!(first, second);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::remote_exchanges::{
     request,
-    response::{Balance, Order, Instrument, OrderData},
+    response::{AmendedOrder, Balance, Fill, Order, Instrument, OrderData, OrdersPage},
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -14,12 +14,55 @@ pub enum Request {
     Balances(request::GeneralBalanceRequest),
     PostOrder(request::GeneralPostOrderRequest),
     OrdersList(request::GeneralOrdersListRequest),
+    // stored by variant index, new requests go after the existing ones
+    CancelOrder(request::GeneralCancelOrderRequest),
+    AmendOrder(request::GeneralAmendOrderRequest),
+    OrderDetails(request::GeneralOrderDetailsRequest),
+    Fills(request::GeneralFillsRequest),
 }
 
-#[derive(Debug, Clone, Deserialize, CandidType)]
+/// requests stored in the first schema version, variants are encoded by index
+#[derive(Deserialize, Serialize)]
+pub enum RequestV1 {
+    Empty,
+    Instruments(request::GeneralInstrumentsRequest),
+    Balances(request::GeneralBalanceRequest),
+    PostOrder(request::GeneralPostOrderRequest),
+    OrdersList(request::GeneralOrdersListRequestV1),
+}
+
+impl From<RequestV1> for Request {
+    fn from(value: RequestV1) -> Self {
+        match value {
+            RequestV1::Empty => Request::Empty,
+            RequestV1::Instruments(r) => Request::Instruments(r),
+            RequestV1::Balances(r) => Request::Balances(r),
+            RequestV1::PostOrder(r) => Request::PostOrder(r),
+            RequestV1::OrdersList(r) => Request::OrdersList(r.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType)]
 pub enum Response {
     Instruments(Vec<Instrument>),
     Balances(Vec<Balance>),
     Order(OrderData),
-    OrdersInfo(Vec<Order>),
+    Cancelled(OrderData),
+    Amended(AmendedOrder),
+    OrderDetails(Order),
+    Fills(Vec<Fill>),
+    OrdersInfo(OrdersPage),
+}
+
+impl Response {
+    /// id the exchange assigned to the order the response is about
+    pub fn order_id(&self) -> Option<String> {
+        match self {
+            Response::Order(o) | Response::Cancelled(o) => Some(o.id.clone()),
+            Response::Amended(o) => Some(o.id.clone()),
+            Response::OrderDetails(o) => Some(o.order_id.clone()),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,226 @@
+use crate::{
+    exchange::Exchange,
+    interval::Interval,
+    memory::{Memory, MemoryLocation, MEMORY_MANAGER},
+    pair::Pair,
+    storable_wrapper::{StorableWrapper, Versioned},
+    volume_store::VOLUME_STORE,
+};
+use candid::CandidType;
+use ic_cdk_timers::TimerId;
+use ic_stable_structures::StableBTreeMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+type JobId = u32;
+type JobStore = StableBTreeMap<JobId, StorableWrapper<Job>, Memory>;
+
+pub const MIN_PERIOD: u64 = 60;
+
+thread_local! {
+    static JOB_STORE: RefCell<JobStore> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryLocation::Jobs.memory_id())),
+        )
+    );
+
+    // timers live on the heap only, they are re-armed from JOB_STORE after upgrade
+    static TIMERS: RefCell<HashMap<JobId, TimerId>> = RefCell::new(HashMap::new());
+    static RUNNING: RefCell<HashSet<JobId>> = RefCell::new(HashSet::new());
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct JobRun {
+    pub timestamp: u64,
+    pub error: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Job {
+    pub exchange: Exchange,
+    pub pair: Pair,
+    pub interval: Interval,
+    /// seconds between runs
+    pub period: u64,
+    pub paused: bool,
+    pub last_run: Option<JobRun>,
+}
+
+impl Versioned for Job {}
+
+/// marks a job as running until dropped, a run that traps after an await
+/// has its future dropped by the cleanup callback, which releases the job too
+struct RunningGuard(JobId);
+
+impl RunningGuard {
+    fn acquire(id: JobId) -> Option<Self> {
+        RUNNING
+            .with_borrow_mut(|r| r.insert(id))
+            .then_some(Self(id))
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.with_borrow_mut(|r| r.remove(&self.0));
+    }
+}
+
+pub struct Scheduler;
+
+impl Scheduler {
+    pub fn add_job(exchange: Exchange, pair: Pair, interval: Interval, period: u64) -> JobId {
+        let id = JOB_STORE.with_borrow_mut(|j| {
+            let id = j.last_key_value().map(|(id, _)| id + 1).unwrap_or_default();
+
+            j.insert(
+                id,
+                StorableWrapper(Job {
+                    exchange,
+                    pair,
+                    interval,
+                    period,
+                    paused: false,
+                    last_run: None,
+                }),
+            );
+
+            id
+        });
+
+        Self::arm(id, period);
+
+        id
+    }
+
+    pub fn remove_job(id: JobId) -> bool {
+        Self::disarm(id);
+
+        JOB_STORE.with_borrow_mut(|j| j.remove(&id).is_some())
+    }
+
+    pub fn get_jobs() -> Vec<(JobId, Job)> {
+        JOB_STORE.with_borrow(|j| j.iter().map(|(id, job)| (id, job.0)).collect())
+    }
+
+    pub fn set_paused(id: JobId, paused: bool) -> bool {
+        let Some(period) = Self::update_job(id, |job| {
+            job.paused = paused;
+            job.period
+        }) else {
+            return false;
+        };
+
+        match paused {
+            true => Self::disarm(id),
+            false => Self::arm(id, period),
+        }
+
+        true
+    }
+
+    /// starts timers of all jobs which are not paused, called after upgrade
+    pub fn arm_all() {
+        for (id, job) in Self::get_jobs() {
+            if !job.paused {
+                Self::arm(id, job.period);
+            }
+        }
+    }
+
+    fn arm(id: JobId, period: u64) {
+        Self::disarm(id);
+
+        let timer_id = ic_cdk_timers::set_timer_interval(Duration::from_secs(period), move || {
+            ic_cdk::spawn(Self::run_job(id))
+        });
+
+        TIMERS.with_borrow_mut(|t| t.insert(id, timer_id));
+    }
+
+    fn disarm(id: JobId) {
+        if let Some(timer_id) = TIMERS.with_borrow_mut(|t| t.remove(&id)) {
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+    }
+
+    fn update_job<R>(id: JobId, f: impl FnOnce(&mut Job) -> R) -> Option<R> {
+        JOB_STORE.with_borrow_mut(|j| {
+            let mut job = j.get(&id)?;
+            let result = f(&mut job);
+            j.insert(id, job);
+
+            Some(result)
+        })
+    }
+
+    async fn run_job(id: JobId) {
+        // previous run may still be waiting for exchange responses
+        let Some(_running) = RunningGuard::acquire(id) else {
+            return;
+        };
+
+        if let Some(job) = JOB_STORE.with_borrow(|j| j.get(&id)) {
+            let error = Self::ingest(&job).await.err();
+
+            Self::update_job(id, |job| {
+                job.last_run = Some(JobRun {
+                    timestamp: ic_cdk::api::time() / 1_000_000_000,
+                    error,
+                });
+            });
+        }
+    }
+
+    /// pulls candles since the last stored one, taker volumes are pulled too
+    /// when volume store of the pair was initialized
+    async fn ingest(job: &Job) -> Result<(), String> {
+        let now = ic_cdk::api::time() / 1_000_000_000;
+        let pair = job.pair.to_string();
+
+        let start = crate::get_last_timestamp(job.exchange, pair.clone(), job.interval)
+            .unwrap_or(now - job.period);
+
+        if start < now {
+            crate::pull_candles(pair.clone(), job.exchange, job.interval, start, now)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+
+        let has_volumes =
+            VOLUME_STORE.with_borrow(|v| v.contains_key(&(job.exchange, job.pair.clone())));
+
+        if has_volumes {
+            crate::pull_volumes(job.exchange, pair, now)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_guard_releases_job() {
+        let guard = RunningGuard::acquire(7).unwrap();
+        assert!(RunningGuard::acquire(7).is_none());
+
+        drop(guard);
+        assert!(RunningGuard::acquire(7).is_some());
+
+        let unwound = std::panic::catch_unwind(|| {
+            let _guard = RunningGuard::acquire(7).unwrap();
+            panic!("run trapped");
+        });
+        assert!(unwound.is_err());
+        assert!(RunningGuard::acquire(7).is_some());
+    }
+}
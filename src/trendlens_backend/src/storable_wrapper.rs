@@ -27,20 +27,48 @@ where
     }
 }
 
+/// size of the version header preceding bincode payload
+pub const VERSION_SIZE: usize = std::mem::size_of::<u16>();
+
+/// schema of a value stored through StorableWrapper, bump VERSION whenever
+/// the layout changes and decode payloads of older versions in migrate
+pub trait Versioned: Serialize + for<'de> Deserialize<'de> {
+    const VERSION: u16 = 1;
+
+    fn migrate(version: u16, _payload: &[u8]) -> Self {
+        panic!("no migration from version {} to {}", version, Self::VERSION)
+    }
+}
+
+/// prepends version header to raw bincode payload
+pub fn envelope(version: u16, payload: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(VERSION_SIZE + payload.len());
+    buf.extend_from_slice(&version.to_le_bytes());
+    buf.extend_from_slice(payload);
+    buf
+}
+
 impl<T> Storable for StorableWrapper<T>
 where
-    T: Serialize + for<'de> Deserialize<'de>,
+    T: Versioned,
 {
     const BOUND: ic_stable_structures::storable::Bound =
         ic_stable_structures::storable::Bound::Unbounded;
 
     fn to_bytes(&self) -> Cow<[u8]> {
-        let buf = bincode::serialize(&self.0).unwrap();
-        Cow::Owned(buf)
+        let payload = bincode::serialize(&self.0).unwrap();
+        Cow::Owned(envelope(T::VERSION, &payload))
     }
 
     fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        StorableWrapper(bincode::deserialize(bytes.as_ref()).unwrap())
+        let (version, payload) = bytes.split_at(VERSION_SIZE);
+        let version = u16::from_le_bytes([version[0], version[1]]);
+
+        if version == T::VERSION {
+            StorableWrapper(bincode::deserialize(payload).unwrap())
+        } else {
+            StorableWrapper(T::migrate(version, payload))
+        }
     }
 }
 
@@ -55,6 +83,35 @@ mod tests {
         c: u64,
     }
 
+    impl Versioned for Test {}
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+    struct TestV2 {
+        a: u32,
+        b: String,
+        c: u64,
+        d: Option<String>,
+    }
+
+    impl Versioned for TestV2 {
+        const VERSION: u16 = 2;
+
+        fn migrate(version: u16, payload: &[u8]) -> Self {
+            match version {
+                1 => {
+                    let v1: Test = bincode::deserialize(payload).unwrap();
+                    TestV2 {
+                        a: v1.a,
+                        b: v1.b,
+                        c: v1.c,
+                        d: None,
+                    }
+                }
+                _ => panic!("unknown version {}", version),
+            }
+        }
+    }
+
     #[test]
     fn test_serialize() {
         let test_struct = Test {
@@ -67,4 +124,42 @@ mod tests {
         let storable_wrapper2: StorableWrapper<Test> = StorableWrapper::from_bytes(bytes);
         assert_eq!(*storable_wrapper, *storable_wrapper2);
     }
+
+    #[test]
+    fn test_decode_older_version() {
+        let test_struct = Test {
+            a: 42,
+            b: "owner".to_string(),
+            c: 1234567890,
+        };
+        let storable_wrapper = StorableWrapper(test_struct);
+        let bytes = storable_wrapper.to_bytes();
+        let migrated: StorableWrapper<TestV2> = StorableWrapper::from_bytes(bytes);
+
+        assert_eq!(
+            *migrated,
+            TestV2 {
+                a: 42,
+                b: "owner".to_string(),
+                c: 1234567890,
+                d: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_legacy_payload() {
+        let test_struct = Test {
+            a: 1,
+            b: "legacy".to_string(),
+            c: 2,
+        };
+        // values written before the version header was introduced are plain bincode
+        let legacy = bincode::serialize(&test_struct).unwrap();
+        let bytes = envelope(1, &legacy);
+        let migrated: StorableWrapper<TestV2> = StorableWrapper::from_bytes(Cow::Owned(bytes));
+
+        assert_eq!(migrated.d, None);
+        assert_eq!(migrated.b, "legacy");
+    }
 }
@@ -3,7 +3,7 @@ use crate::{
     exchange::{Exchange, TimeVolume},
     memory::{Memory, MemoryLocation, MEMORY_MANAGER},
     pair::Pair,
-    storable_wrapper::StorableWrapper,
+    storable_wrapper::{StorableWrapper, Versioned},
 };
 use ic_stable_structures::StableBTreeMap;
 use serde::{Deserialize, Serialize};
@@ -35,6 +35,34 @@ impl VolumesStore {
     }
 }
 
+/// volumes stored before taker buy/sell split
+#[derive(Deserialize, Serialize)]
+struct TimeVolumeV1 {
+    timestamp: u64,
+    volume: f64,
+}
+
+impl Versioned for VolumesStore {
+    const VERSION: u16 = 2;
+
+    fn migrate(version: u16, payload: &[u8]) -> Self {
+        match version {
+            1 => {
+                let volumes: BTreeMap<Timestamp, TimeVolumeV1> =
+                    bincode::deserialize(payload).unwrap();
+
+                VolumesStore(
+                    volumes
+                        .into_iter()
+                        .map(|(t, v)| (t, TimeVolume::new(v.timestamp, v.volume)))
+                        .collect(),
+                )
+            }
+            _ => panic!("unknown volumes store version {}", version),
+        }
+    }
+}
+
 impl Deref for VolumesStore {
     type Target = BTreeMap<Timestamp, TimeVolume>;
 
@@ -65,3 +93,28 @@ impl TimestampBased for VolumesStore {
             .collect::<Vec<_>>()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storable_wrapper::envelope;
+    use ic_stable_structures::Storable;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_decode_v1_volumes() {
+        let mut volumes: BTreeMap<Timestamp, TimeVolumeV1> = BTreeMap::new();
+        volumes.insert(
+            60,
+            TimeVolumeV1 {
+                timestamp: 60,
+                volume: 2.0,
+            },
+        );
+
+        let bytes = envelope(1, &bincode::serialize(&volumes).unwrap());
+        let store: StorableWrapper<VolumesStore> = StorableWrapper::from_bytes(Cow::Owned(bytes));
+
+        assert_eq!(store.get(&60), Some(&TimeVolume::new(60, 2.0)));
+    }
+}
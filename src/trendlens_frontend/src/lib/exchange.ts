@@ -3,6 +3,8 @@ import type { Exchange } from "../../../declarations/trendlens_backend/trendlens
 export enum Exchanges {
   Okx = "Okx",
   Coinbase = "Coinbase",
+  Binance = "Binance",
+  Kraken = "Kraken",
 }
 
 export type ExchangeKey = keyof typeof Exchanges;
@@ -21,11 +23,17 @@ export const handleExchange = (exchange: Exchanges): Exchange => {
       return { Okx: null };
     case Exchanges.Coinbase:
       return { Coinbase: null };
+    case Exchanges.Binance:
+      return { Binance: null };
+    case Exchanges.Kraken:
+      return { Kraken: null };
   }
 };
 
 export const toExchanges = (exchange: Exchange): Exchanges => {
   if (Object.keys(exchange)[0] == "Okx") return Exchanges.Okx;
   if (Object.keys(exchange)[0] == "Coinbase") return Exchanges.Coinbase;
+  if (Object.keys(exchange)[0] == "Binance") return Exchanges.Binance;
+  if (Object.keys(exchange)[0] == "Kraken") return Exchanges.Kraken;
   throw new Error("Invalid exchange");
 };
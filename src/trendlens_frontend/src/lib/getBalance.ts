@@ -2,7 +2,7 @@ import { Exchanges, handleExchange } from "./exchange";
 import { keyStore } from "./keystore.svelte";
 import { isBalanceResponse } from "./response";
 import { extractOkValue } from "./result";
-import { finishSignature } from "./signature";
+import { finishSignature, signingTimestamps } from "./signature";
 import { wallet } from "./wallet.svelte";
 
 export const getBalance = async (
@@ -36,14 +36,13 @@ export const getBalance = async (
     },
   ]);
 
-  const timestamp = Math.round(Date.now() / 1000) - 4;
-  const isoTimestamp = new Date().toISOString();
+  const [timestamp, isoTimestamp] = signingTimestamps();
 
   const signature = await finishSignature(
     exchange,
     instructions[0].signature,
     key.secretKey,
-    exchange == Exchanges.Coinbase ? timestamp.toString() : isoTimestamp,
+    [timestamp, isoTimestamp],
   );
 
   const result = await wallet.actor.run_transaction(
@@ -22,7 +22,7 @@ import {
   isExchangeErr,
   isHttpApiClientError,
 } from "./result";
-import { finishSignature } from "./signature";
+import { finishSignature, signingTimestamps } from "./signature";
 import { wallet } from "./wallet.svelte";
 
 export class PostOrderRequest {
@@ -187,14 +187,13 @@ export const executeRequest = async (
 
   const [requestNumber, instructions] = await postRequest(exchange, request);
 
-  const timestamp = Math.round(Date.now() / 1000) - 4;
-  const isoTimestamp = new Date().toISOString();
+  const [timestamp, isoTimestamp] = signingTimestamps();
 
   const signature = await finishSignature(
     exchange,
     instructions[0].signature,
     key.secretKey,
-    exchange == Exchanges.Coinbase ? timestamp.toString() : isoTimestamp,
+    [timestamp, isoTimestamp],
   );
 
   const result = await wallet.actor.run_transaction(
@@ -3,6 +3,7 @@ import type {
   GeneralInstrumentsRequest,
   GeneralPostOrderRequest,
   Request,
+  SignableInstruction,
 } from "../../../declarations/trendlens_backend/trendlens_backend.did";
 
 export enum RequestType {
@@ -266,3 +267,11 @@ export function isBalancesRequest(
     (request as { Balances: GeneralBalanceRequest }).Balances !== undefined
   );
 }
+
+export function isExecuted(instruction: SignableInstruction): boolean {
+  return "Succeeded" in instruction.status;
+}
+
+export function statusName(instruction: SignableInstruction): string {
+  return Object.keys(instruction.status)[0];
+}
@@ -1,8 +1,8 @@
 import type {
   Balance,
   Instrument,
-  Order,
   OrderData,
+  OrdersPage,
   Response,
 } from "../../../declarations/trendlens_backend/trendlens_backend.did";
 
@@ -28,6 +28,6 @@ export function isBalanceResponse(
 
 export function isOrdersResponse(
   response: Response,
-): response is { OrdersInfo: Array<Order> } {
-  return (response as { OrdersInfo: Array<Order> }).OrdersInfo !== undefined;
+): response is { OrdersInfo: OrdersPage } {
+  return (response as { OrdersInfo: OrdersPage }).OrdersInfo !== undefined;
 }
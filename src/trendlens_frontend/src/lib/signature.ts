@@ -9,28 +9,118 @@ function arrayBufferToBase64(buffer: ArrayBuffer) {
   return Buffer.from(buffer).toString("base64");
 }
 
-// this is not generic right now, but solely based on the requirements of the OKX api
-export const finishSignature = async (
-  exchange: Exchanges,
-  signatureData: string,
-  secret: string,
-  timestamp: string,
-): Promise<string> => {
-  const preHashString = timestamp + signatureData;
+// both timestamps come from the same instant, the canister rejects them when they
+// drift apart; backdated a second so exchanges never see a time ahead of theirs
+export const signingTimestamps = (): [number, string] => {
+  const now = new Date(Date.now() - 1000);
 
-  const keyData = base64ToArrayBuffer(
-    exchange == Exchanges.Okx ? btoa(secret) : secret,
-  );
+  return [Math.floor(now.getTime() / 1000), now.toISOString()];
+};
+
+const hmac = async (
+  hash: "SHA-256" | "SHA-512",
+  keyData: Uint8Array,
+  message: Uint8Array,
+): Promise<ArrayBuffer> => {
   const cryptoKey = await crypto.subtle.importKey(
     "raw",
     keyData,
-    { name: "HMAC", hash: "SHA-256" },
+    { name: "HMAC", hash },
     false,
     ["sign"],
   );
 
-  const messageBuffer = new TextEncoder().encode(preHashString);
-  const signature = await crypto.subtle.sign("HMAC", cryptoKey, messageBuffer);
+  return crypto.subtle.sign("HMAC", cryptoKey, message);
+};
+
+const hmacSha256 = (keyData: Uint8Array, message: string) =>
+  hmac("SHA-256", keyData, new TextEncoder().encode(message));
+
+// kraken payload is path + nonce + postdata, it signs path + sha256(nonce + postdata)
+const krakenSignature = async (payload: string, secret: string) => {
+  const parts = payload.match(/^(\/0\/private\/[A-Za-z]+)(.*)$/s);
+
+  if (!parts) {
+    throw new Error("kraken payload does not start with a private path");
+  }
+
+  const encoder = new TextEncoder();
+  const digest = await crypto.subtle.digest(
+    "SHA-256",
+    encoder.encode(parts[2]),
+  );
+  const message = Buffer.concat([
+    encoder.encode(parts[1]),
+    Buffer.from(digest),
+  ]);
+
+  return arrayBufferToBase64(
+    await hmac("SHA-512", base64ToArrayBuffer(secret), message),
+  );
+};
+
+// timestamp is the iso string for okx and seconds for the other exchanges
+const signPayload = async (
+  exchange: Exchanges,
+  payload: string,
+  secret: string,
+  timestamp: string,
+): Promise<string> => {
+  switch (exchange) {
+    case Exchanges.Okx:
+      return arrayBufferToBase64(
+        await hmacSha256(new TextEncoder().encode(secret), timestamp + payload),
+      );
+    case Exchanges.Coinbase:
+      return arrayBufferToBase64(
+        await hmacSha256(base64ToArrayBuffer(secret), timestamp + payload),
+      );
+    case Exchanges.Binance:
+      // payload ends with "timestamp=", binance expects milliseconds and hex
+      return Buffer.from(
+        await hmacSha256(
+          new TextEncoder().encode(secret),
+          payload + Number(timestamp) * 1000,
+        ),
+      ).toString("hex");
+    case Exchanges.Kraken:
+      return krakenSignature(payload, secret);
+  }
+};
+
+// kraken nonce of the instruction at `position`, same as Kraken::execution_nonce
+const krakenNonce = (isoTimestamp: string, position: number) =>
+  (Date.parse(isoTimestamp) * 1000 + position).toString();
+
+// instructions executed as multiple requests carry one payload per line,
+// `position` is the index of the instruction within its transaction
+export const finishSignature = async (
+  exchange: Exchanges,
+  signatureData: string,
+  secret: string,
+  [timestamp, isoTimestamp]: [number, string],
+  position = 0,
+): Promise<string> => {
+  const payloads =
+    exchange == Exchanges.Kraken
+      ? signatureData.replaceAll(
+          "{nonce}",
+          krakenNonce(isoTimestamp, position),
+        )
+      : signatureData;
+
+  const signatures = await Promise.all(
+    payloads
+      .split("\n")
+      .map((payload) =>
+        signPayload(
+          exchange,
+          payload,
+          secret,
+          exchange == Exchanges.Okx ? isoTimestamp : timestamp.toString(),
+        ),
+      ),
+  );
 
-  return arrayBufferToBase64(signature);
+  return signatures.join("\n");
 };